@@ -1,11 +1,37 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use color_eyre::eyre::{bail, eyre, Result};
+use std::path::{Path, PathBuf};
 
+use crate::config;
 use crate::core;
+use crate::explore;
+use crate::parsers::{self, ParseError};
+use tracing_subscriber::EnvFilter;
 
 #[derive(Parser, Debug)]
 #[command(name = "aoc25", version, about = "Advent of Code 2025 runner")] 
 pub struct Cli {
+    /// Path to the project config (default: nearest `aoc.toml` in this or a parent directory)
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Puzzle year (overrides `year` in aoc.toml)
+    #[arg(long, global = true)]
+    year: Option<u16>,
+    /// Directory of cached inputs (overrides `inputs_dir` in aoc.toml)
+    #[arg(long, global = true)]
+    inputs_dir: Option<PathBuf>,
+    /// Input file name pattern, e.g. `day{day:02}.txt` (overrides `input_pattern` in aoc.toml)
+    #[arg(long, global = true)]
+    input_pattern: Option<String>,
+    /// HTTP user agent for online requests (overrides `user_agent` in aoc.toml)
+    #[arg(long, global = true)]
+    user_agent: Option<String>,
+    /// Show the first N input lines and parsed records while solving (default 5)
+    #[arg(long, global = true, env = "AOC_PREVIEW", value_name = "N")]
+    preview: Option<usize>,
+    /// No previews or informational logging, just answers and errors (wins over --preview)
+    #[arg(long, short, global = true)]
+    quiet: bool,
     #[command(subcommand)]
     cmd: Command,
 }
@@ -20,8 +46,11 @@ enum Command {
         #[arg(value_enum)]
         part: Option<Part>,
         /// Submit the computed answer to AoC (requires --features online)
-        #[arg(long)]
+        #[arg(long, conflicts_with = "example")]
         submit: bool,
+        /// Check the day's registered examples instead of running the real input
+        #[arg(long)]
+        example: bool,
     },
     /// Fetch and cache the puzzle input for a day
     Fetch {
@@ -33,6 +62,44 @@ enum Command {
     },
     /// Print the path to the input file for a day
     InputPath { day: u8 },
+    /// Explore a day's input interactively with the `parsers` helpers
+    Explore {
+        /// Day number (1..=25)
+        day: u8,
+    },
+    /// Manage the encrypted input cache (key from AOC_INPUT_KEY)
+    Inputs {
+        #[command(subcommand)]
+        action: InputsCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum InputsCommand {
+    /// Encrypt plaintext `dayXX.txt` inputs to `dayXX.txt.enc`
+    Encrypt {
+        /// Only this day (default: every day with a plaintext input)
+        day: Option<u8>,
+        /// Directory holding the inputs (default: the input cache)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        /// Keep the plaintext files instead of removing them
+        #[arg(long)]
+        keep: bool,
+    },
+    /// Decrypt `dayXX.txt.enc` inputs back to plaintext `dayXX.txt`
+    Decrypt {
+        /// Only this day (default: every day with an encrypted input)
+        day: Option<u8>,
+        /// Directory holding the inputs (default: the input cache)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        /// Keep the encrypted files instead of removing them
+        #[arg(long)]
+        keep: bool,
+    },
+    /// Print a new random key suitable for AOC_INPUT_KEY
+    Keygen,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, ValueEnum)]
@@ -52,13 +119,58 @@ impl From<Part> for u8 {
 
 pub fn main() -> Result<()> {
     let cli = Cli::parse();
+    init_logging(cli.quiet, cli.preview);
+    config::init(&config::Overrides {
+        config: cli.config,
+        year: cli.year,
+        inputs_dir: cli.inputs_dir,
+        input_pattern: cli.input_pattern,
+        user_agent: cli.user_agent,
+    })?;
     match cli.cmd {
-        Command::Run { day, part, submit } => run_day(day, part, submit),
+        Command::Run { day, part, example: true, .. } => run_examples(day, part),
+        Command::Run { day, part, submit, .. } => run_day(day, part, submit),
         Command::Fetch { day, force } => fetch(day, force),
         Command::InputPath { day } => {
             println!("{}", core::input_path(day).display());
             Ok(())
         }
+        Command::Explore { day } => explore::explore(day),
+        Command::Inputs { action } => inputs(action),
+    }
+}
+
+/// Previews and diagnostics go to stderr through `tracing`, inside `day`/`part` spans. `AOC_LOG`
+/// takes an `EnvFilter` directive list: a level (`error`, `warn`, `info`, `debug`, `trace`;
+/// default `info`) and/or per-module levels such as `warn,aoc25::day05=debug`.
+fn init_logging(quiet: bool, preview: Option<usize>) {
+    let mut invalid = None;
+    let filter = match std::env::var("AOC_LOG") {
+        _ if quiet => EnvFilter::new("warn"),
+        Ok(directives) => EnvFilter::try_new(&directives).unwrap_or_else(|e| {
+            invalid = Some((directives, e));
+            EnvFilter::new("info")
+        }),
+        Err(_) => EnvFilter::new("info"),
+    };
+    parsers::set_preview(preview_lines(quiet, preview));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .without_time()
+        .with_writer(std::io::stderr)
+        .init();
+    if let Some((directives, e)) = invalid {
+        tracing::warn!("ignoring AOC_LOG={directives:?} ({e}); logging at info");
+    }
+}
+
+/// How many preview lines to show: none when quiet, even if `AOC_PREVIEW` is exported.
+fn preview_lines(quiet: bool, preview: Option<usize>) -> usize {
+    if quiet {
+        0
+    } else {
+        preview.unwrap_or(parsers::DEFAULT_PREVIEW)
     }
 }
 
@@ -73,12 +185,17 @@ fn run_day(day: u8, part: Option<Part>, submit: bool) -> Result<()> {
     };
 
     let input = core::read_or_fetch_input(day)?;
+    if let core::InputCheck::Mismatch { expected, actual } = core::check_input_hash(day, &input)? {
+        tracing::warn!(
+            "input for day {day} does not match {} (expected {expected}, got {actual}); was it edited? Re-fetch with `fetch {day} --force`.",
+            core::manifest_path().display()
+        );
+    }
 
+    let _day = tracing::info_span!("day", day).entered();
     let run_part = |p: Part| -> Result<String> {
-        match p {
-            Part::P1 => solution.part1(&input),
-            Part::P2 => solution.part2(&input),
-        }
+        let _part = tracing::info_span!("part", part = u8::from(p)).entered();
+        core::run_part(solution, day, u8::from(p), &input)
     };
 
     match part {
@@ -90,18 +207,25 @@ fn run_day(day: u8, part: Option<Part>, submit: bool) -> Result<()> {
             }
         }
         None => {
-            // Try both parts
-            if let Ok(ans) = run_part(Part::P1) {
-                println!("Day {day} Part 1: {ans}");
-                if submit {
-                    submit_answer(day, Part::P1, &ans)?;
+            // Try both parts; unimplemented parts are skipped quietly but bad input is an error.
+            let mut bad_input = None;
+            for p in [Part::P1, Part::P2] {
+                match run_part(p) {
+                    Ok(ans) => {
+                        println!("Day {day} Part {}: {ans}", u8::from(p));
+                        if submit {
+                            submit_answer(day, p, &ans)?;
+                        }
+                    }
+                    Err(e) if e.downcast_ref::<ParseError>().is_some() => {
+                        tracing::error!("Day {day} Part {}: {e:#}", u8::from(p));
+                        bad_input.get_or_insert(e);
+                    }
+                    Err(_) => {}
                 }
             }
-            if let Ok(ans) = run_part(Part::P2) {
-                println!("Day {day} Part 2: {ans}");
-                if submit {
-                    submit_answer(day, Part::P2, &ans)?;
-                }
+            if let Some(e) = bad_input {
+                return Err(e);
             }
         }
     }
@@ -109,17 +233,79 @@ fn run_day(day: u8, part: Option<Part>, submit: bool) -> Result<()> {
     Ok(())
 }
 
+fn run_examples(day: u8, part: Option<Part>) -> Result<()> {
+    let outcomes = core::run_examples(day, part.map(u8::from))?;
+    if outcomes.is_empty() {
+        bail!("No examples registered for day {day}. Add them in the day's `Solution::examples`.");
+    }
+    let mut failed = 0;
+    for o in &outcomes {
+        let status = if o.passed() { "ok" } else { failed += 1; "FAILED" };
+        match &o.actual {
+            Ok(ans) => println!("Day {day} Example {} Part {}: {ans} (expected {}) {status}", o.index + 1, o.part, o.expected),
+            Err(e) => println!("Day {day} Example {} Part {}: error: {e} (expected {}) {status}", o.index + 1, o.part, o.expected),
+        }
+    }
+    if failed > 0 {
+        bail!("{failed} of {} example checks failed", outcomes.len());
+    }
+    Ok(())
+}
+
 fn fetch(day: u8, force: bool) -> Result<()> {
     if !(1..=25).contains(&day) { bail!("day must be in 1..=25"); }
-    core::ensure_inputs_dir()?;
-    let path = core::input_path(day);
-    if path.exists() && !force { bail!("Input already exists at {} (use --force to overwrite)", path.display()); }
+    if core::input_cached(day) && !force {
+        bail!("Input already exists for day {day} in {} (use --force to overwrite)", core::input_path(day).display());
+    }
     let contents = core::fetch_input(day)?;
-    std::fs::write(&path, contents)?;
-    eprintln!("Saved input to {}", path.display());
+    let path = core::cache_input(day, &contents)?;
+    tracing::info!("Saved input to {}", path.display());
     Ok(())
 }
 
+fn inputs(action: InputsCommand) -> Result<()> {
+    let key = || {
+        core::input_key()?.ok_or_else(|| eyre!("{} must be set (try `aoc25 inputs keygen`)", core::INPUT_KEY_VAR))
+    };
+    match action {
+        InputsCommand::Encrypt { day, dir, keep } => {
+            let key = key()?;
+            for plain in input_files(dir.as_deref(), day, false)? {
+                let target = core::encrypt_file(&plain, &key, keep)?;
+                eprintln!("Encrypted {} -> {}", plain.display(), target.display());
+            }
+        }
+        InputsCommand::Decrypt { day, dir, keep } => {
+            let key = key()?;
+            for plain in input_files(dir.as_deref(), day, true)? {
+                core::decrypt_file(&plain, &key, keep)?;
+                eprintln!("Decrypted {}", plain.display());
+            }
+        }
+        InputsCommand::Keygen => println!("{}", core::generate_key()),
+    }
+    Ok(())
+}
+
+/// Plaintext paths (per `input_pattern`) in `dir` of the days that have a plaintext (or, if `encrypted`,
+/// an encrypted) input file.
+fn input_files(dir: Option<&Path>, day: Option<u8>, encrypted: bool) -> Result<Vec<PathBuf>> {
+    let dir = dir.unwrap_or(core::inputs_dir());
+    let days: Vec<u8> = match day {
+        Some(d) => vec![d],
+        None => (1..=25).collect(),
+    };
+    let found: Vec<PathBuf> = days
+        .into_iter()
+        .map(|d| dir.join(core::input_file_name(d)))
+        .filter(|p| if encrypted { core::encrypted_path_for(p).exists() } else { p.exists() })
+        .collect();
+    if found.is_empty() {
+        bail!("No matching input files found in {}", dir.display());
+    }
+    Ok(found)
+}
+
 fn submit_answer(day: u8, part: Part, answer: &str) -> Result<()> {
     #[cfg(feature = "online")]
     {
@@ -132,3 +318,21 @@ fn submit_answer(day: u8, part: Part, answer: &str) -> Result<()> {
         Err(eyre!("Submission requires building with --features online"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_wins_over_preview_from_the_environment() {
+        // SAFETY: only this test touches AOC_PREVIEW, and the other tests only read the environment
+        // through `std::env`, which takes the same lock as `set_var`.
+        unsafe { std::env::set_var("AOC_PREVIEW", "3") };
+        let cli = Cli::try_parse_from(["aoc25", "--quiet", "input-path", "1"]).unwrap();
+        assert_eq!((cli.quiet, cli.preview), (true, Some(3)));
+        assert_eq!(preview_lines(cli.quiet, cli.preview), 0);
+        let cli = Cli::try_parse_from(["aoc25", "input-path", "1"]).unwrap();
+        assert_eq!(preview_lines(cli.quiet, cli.preview), 3);
+        unsafe { std::env::remove_var("AOC_PREVIEW") };
+    }
+}
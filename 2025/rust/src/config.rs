@@ -0,0 +1,191 @@
+//! Project layout configuration from `aoc.toml`.
+//!
+//! The config file is discovered by walking up from the current directory, so the runner works
+//! from any subdirectory of the project. Precedence: CLI flags > `aoc.toml` > built-in defaults.
+//! Relative paths in `aoc.toml`, and the default `inputs` directory when a config file is found,
+//! are resolved against the directory containing the file.
+
+use color_eyre::eyre::{Result, WrapErr, bail, eyre};
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Resolved settings used by `core` for input caching and online requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub year: u16,
+    /// Directory holding cached inputs and the hash manifest.
+    pub inputs_dir: PathBuf,
+    /// File name pattern for a day's input. Supports `{day}`, `{day:02}` and `{year}`.
+    pub input_pattern: String,
+    pub user_agent: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: 2025,
+            inputs_dir: PathBuf::from("inputs"),
+            input_pattern: "day{day:02}.txt".to_string(),
+            user_agent: "aoc25-rust (https://github.com/craigjperry2)".to_string(),
+        }
+    }
+}
+
+/// The optional keys accepted in `aoc.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    year: Option<u16>,
+    inputs_dir: Option<PathBuf>,
+    input_pattern: Option<String>,
+    user_agent: Option<String>,
+}
+
+/// Settings given on the command line; each one that is set wins over the config file.
+#[derive(Debug, Default, Clone)]
+pub struct Overrides {
+    pub config: Option<PathBuf>,
+    pub year: Option<u16>,
+    pub inputs_dir: Option<PathBuf>,
+    pub input_pattern: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl Config {
+    /// Apply a parsed config file (found in `base_dir`) and then the CLI overrides to the defaults.
+    fn resolve(file: Option<(&Path, ConfigFile)>, overrides: &Overrides) -> Result<Self> {
+        let mut config = Config::default();
+        if let Some((base_dir, file)) = file {
+            if let Some(year) = file.year {
+                config.year = year;
+            }
+            // The default `inputs/` also lives beside the config file, not in the cwd.
+            config.inputs_dir = base_dir.join(file.inputs_dir.unwrap_or(config.inputs_dir));
+            if let Some(pattern) = file.input_pattern {
+                config.input_pattern = pattern;
+            }
+            if let Some(agent) = file.user_agent {
+                config.user_agent = agent;
+            }
+        }
+        if let Some(year) = overrides.year {
+            config.year = year;
+        }
+        if let Some(dir) = &overrides.inputs_dir {
+            config.inputs_dir = dir.clone();
+        }
+        if let Some(pattern) = &overrides.input_pattern {
+            config.input_pattern = pattern.clone();
+        }
+        if let Some(agent) = &overrides.user_agent {
+            config.user_agent = agent.clone();
+        }
+        if !config.input_pattern.contains("{day}") && !config.input_pattern.contains("{day:02}") {
+            bail!(
+                "input_pattern {:?} must contain {{day}} or {{day:02}}",
+                config.input_pattern
+            );
+        }
+        Ok(config)
+    }
+
+    /// The input file name for `day` according to `input_pattern`.
+    pub fn input_file_name(&self, day: u8) -> String {
+        self.input_pattern
+            .replace("{day:02}", &format!("{day:02}"))
+            .replace("{day}", &day.to_string())
+            .replace("{year}", &self.year.to_string())
+    }
+}
+
+/// Walk up from `start` looking for `aoc.toml`.
+pub fn find_config(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|p| p.is_file())
+}
+
+fn load_file(path: &Path) -> Result<ConfigFile> {
+    let text = fs::read_to_string(path).wrap_err_with(|| format!("reading {}", path.display()))?;
+    toml::from_str(&text).wrap_err_with(|| format!("parsing {}", path.display()))
+}
+
+static CONFIG: OnceCell<Config> = OnceCell::new();
+
+/// Resolve the config (explicit `--config` path, else discovery from the current directory)
+/// and install it for the rest of the run. Call once at startup.
+pub fn init(overrides: &Overrides) -> Result<&'static Config> {
+    let path = match &overrides.config {
+        Some(p) => Some(p.clone()),
+        None => find_config(&std::env::current_dir()?),
+    };
+    let file = match &path {
+        Some(p) => Some((p.parent().unwrap_or(Path::new(".")), load_file(p)?)),
+        None => None,
+    };
+    let config = Config::resolve(file, overrides)?;
+    CONFIG
+        .set(config)
+        .map_err(|_| eyre!("config already initialised"))?;
+    Ok(current())
+}
+
+/// The active config; defaults if `init` has not been called (e.g. in unit tests).
+pub fn current() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_substitutes_day_and_year() {
+        let config = Config {
+            input_pattern: "{year}/input-{day}.txt".to_string(),
+            ..Config::default()
+        };
+        assert_eq!(config.input_file_name(7), "2025/input-7.txt");
+        assert_eq!(Config::default().input_file_name(7), "day07.txt");
+    }
+
+    #[test]
+    fn precedence_is_flags_then_file_then_defaults() {
+        let file: ConfigFile =
+            toml::from_str("year = 2024\ninputs_dir = \"data\"\nuser_agent = \"me\"").unwrap();
+        let overrides = Overrides {
+            year: Some(2023),
+            ..Overrides::default()
+        };
+        let config = Config::resolve(Some((Path::new("/proj"), file)), &overrides).unwrap();
+        assert_eq!(config.year, 2023);
+        assert_eq!(config.inputs_dir, Path::new("/proj/data"));
+        assert_eq!(config.user_agent, "me");
+        assert_eq!(config.input_pattern, Config::default().input_pattern);
+    }
+
+    #[test]
+    fn default_inputs_dir_sits_beside_the_config() {
+        let file: ConfigFile = toml::from_str("year = 2024").unwrap();
+        let config =
+            Config::resolve(Some((Path::new("/tmp"), file)), &Overrides::default()).unwrap();
+        assert_eq!(config.inputs_dir, Path::new("/tmp/inputs"));
+        let config = Config::resolve(None, &Overrides::default()).unwrap();
+        assert_eq!(config.inputs_dir, Path::new("inputs"));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_bad_patterns() {
+        assert!(toml::from_str::<ConfigFile>("inputs = \"x\"").is_err());
+        let overrides = Overrides {
+            input_pattern: Some("input.txt".to_string()),
+            ..Overrides::default()
+        };
+        assert!(Config::resolve(None, &overrides).is_err());
+    }
+}
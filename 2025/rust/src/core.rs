@@ -1,5 +1,9 @@
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
 use color_eyre::eyre::{bail, eyre, Result};
-use once_cell::sync::Lazy;
+use memmap2::Mmap;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -10,55 +14,187 @@ pub trait Solution: Sync + Send {
     fn part2(&self, _input: &str) -> Result<String> {
         bail!("Part 2 not implemented for this day")
     }
+    /// Puzzle examples with their expected answers, checked by `run <day> --example` and by a
+    /// generated `cargo test` per registered day.
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+}
+
+/// A puzzle example and its expected answers. A part left as `None` is not checked, e.g. when
+/// part 2 uses a different example.
+#[derive(Debug, Clone, Copy)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+/// Outcome of running one part of one example.
+#[derive(Debug)]
+pub struct ExampleOutcome {
+    /// Zero-based index into `Solution::examples`.
+    pub index: usize,
+    pub part: u8,
+    pub expected: &'static str,
+    pub actual: Result<String>,
 }
 
-use crate::{day01, day02, day03, day04, day05, day06};
-static DAY01: day01::Day01 = day01::Day01;
-static DAY02: day02::Day02 = day02::Day02;
-static DAY03: day03::Day03 = day03::Day03;
-static DAY04: day04::Day04 = day04::Day04;
-static DAY05: day05::Day05 = day05::Day05;
-static DAY06: day06::Day06 = day06::Day06;
+impl ExampleOutcome {
+    pub fn passed(&self) -> bool {
+        matches!(&self.actual, Ok(a) if a == self.expected)
+    }
+}
 
-pub fn solution_for(day: u8) -> Option<&'static dyn Solution> {
-    match day {
-        1 => Some(&DAY01),
-        2 => Some(&DAY02),
-        3 => Some(&DAY03),
-        4 => Some(&DAY04),
-        5 => Some(&DAY05),
-        6 => Some(&DAY06),
-        _ => None,
+/// Run the registered examples for `day`, optionally restricted to one part.
+pub fn run_examples(day: u8, part: Option<u8>) -> Result<Vec<ExampleOutcome>> {
+    let solution = solution_for(day).ok_or_else(|| eyre!("No solution registered for day {day}"))?;
+    let mut outcomes = Vec::new();
+    for (index, example) in solution.examples().iter().enumerate() {
+        let parts = [(1, example.part1), (2, example.part2)];
+        for (p, expected) in parts {
+            let Some(expected) = expected else { continue };
+            if part.is_some_and(|only| only != p) {
+                continue;
+            }
+            let _span = tracing::info_span!("example", day, example = index + 1, part = p).entered();
+            let actual = run_part(solution, day, p, example.input);
+            outcomes.push(ExampleOutcome { index, part: p, expected, actual });
+        }
     }
+    Ok(outcomes)
+}
+
+/// Run one part of a day's solution. A `ParseError` from the solution, which only sees its input
+/// text, is re-reported naming the day.
+pub fn run_part(solution: &dyn Solution, day: u8, part: u8, input: &str) -> Result<String> {
+    let answer = match part {
+        1 => solution.part1(input),
+        _ => solution.part2(input),
+    };
+    answer.map_err(|e| match e.downcast::<ParseError>() {
+        Ok(err) => err.in_day(day).report(),
+        Err(e) => e,
+    })
 }
 
+/// Register day solutions: `day => module::Type`. Generates `solution_for` and, under `cargo test`, one `examples_dayXX` test per day that checks its `examples()`.
+macro_rules! solutions {
+    ($($day:literal => $module:ident :: $ty:ident),* $(,)?) => {
+        pub fn solution_for(day: u8) -> Option<&'static dyn Solution> {
+            match day {
+                $($day => Some(&crate::$module::$ty),)*
+                _ => None,
+            }
+        }
+
+        #[cfg(test)]
+        mod example_tests {
+            $(
+                #[test]
+                fn $module() {
+                    super::assert_examples_pass($day);
+                }
+            )*
+        }
+    };
+}
+
+solutions! {
+    1 => day01::Day01,
+    2 => day02::Day02,
+    3 => day03::Day03,
+    4 => day04::Day04,
+    5 => day05::Day05,
+    6 => day06::Day06,
+}
+
+#[cfg(test)]
+fn assert_examples_pass(day: u8) {
+    let failures: Vec<String> = run_examples(day, None)
+        .unwrap()
+        .into_iter()
+        .filter(|o| !o.passed())
+        .map(|o| format!("example {} part {}: expected {}, got {:?}", o.index + 1, o.part, o.expected, o.actual))
+        .collect();
+    assert!(failures.is_empty(), "day {day}:\n{}", failures.join("\n"));
+}
+
+use crate::config;
+use crate::parsers::ParseError;
+
 pub fn year() -> u16 {
-    2025
+    config::current().year
 }
 
-static INPUTS_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("inputs"));
+/// Directory of cached inputs, from `aoc.toml`/`--inputs-dir` (default `inputs/`).
+pub fn inputs_dir() -> &'static Path {
+    &config::current().inputs_dir
+}
 
+/// Create the inputs directory, with a `.gitignore` that keeps plaintext inputs out of git
+/// wherever `inputs_dir` points.
 pub fn ensure_inputs_dir() -> Result<()> {
-    fs::create_dir_all(&*INPUTS_DIR)?;
+    fs::create_dir_all(inputs_dir())?;
+    write_inputs_gitignore(inputs_dir())
+}
+
+/// Ignore everything in an inputs directory except encrypted inputs and the hash manifest.
+/// Subdirectories are re-included so `input_pattern`s like `{year}/day{day:02}.txt` work.
+const INPUTS_GITIGNORE: &str = "\
+# Written by aoc25: plaintext inputs stay local, encrypted `*.enc` inputs may be committed.
+*
+!*/
+!.gitignore
+!*.enc
+!manifest.sha256
+";
+
+fn write_inputs_gitignore(dir: &Path) -> Result<()> {
+    let path = dir.join(".gitignore");
+    if !path.exists() {
+        fs::write(path, INPUTS_GITIGNORE)?;
+    }
     Ok(())
 }
 
+/// File name of a day's input, following the configured `input_pattern` (default `dayXX.txt`).
+pub fn input_file_name(day: u8) -> String {
+    config::current().input_file_name(day)
+}
+
 pub fn input_path(day: u8) -> PathBuf {
-    let fname = format!("day{day:02}.txt");
-    INPUTS_DIR.join(fname)
+    inputs_dir().join(input_file_name(day))
 }
 
-pub fn read_or_fetch_input(day: u8) -> Result<String> {
+/// Path of the encrypted copy of a day's input, e.g. `inputs/day01.txt.enc`.
+pub fn encrypted_input_path(day: u8) -> PathBuf {
+    encrypted_path_for(&input_path(day))
+}
+
+/// Read the cached input for `day` (see `read_cached_input`). When neither the plaintext nor the
+/// encrypted copy exists the input is fetched (with `--features online`) and cached via
+/// `cache_input`.
+pub fn read_or_fetch_input(day: u8) -> Result<Input> {
     let path = input_path(day);
-    if path.exists() {
-        Ok(fs::read_to_string(path)?)
+    let encrypted = encrypted_input_path(day);
+    let recorded = read_manifest()?.remove(&input_file_name(day));
+    let key = || {
+        input_key()?.ok_or_else(|| {
+            eyre!(
+                "Found encrypted input at {} but {INPUT_KEY_VAR} is not set.",
+                encrypted.display()
+            )
+        })
+    };
+    if let Some(input) = read_cached_input(&path, &encrypted, recorded.as_deref(), key)? {
+        Ok(input)
     } else {
         #[cfg(feature = "online")]
         {
-            ensure_inputs_dir()?;
             let contents = fetch_input(day)?;
-            fs::write(&path, &contents)?;
-            Ok(contents)
+            cache_input(day, &contents)?;
+            Ok(Input::Text(contents))
         }
         #[cfg(not(feature = "online"))]
         {
@@ -70,6 +206,300 @@ pub fn read_or_fetch_input(day: u8) -> Result<String> {
     }
 }
 
+/// The cached input at `plain`, or decrypted from `encrypted`; `None` if neither exists. The
+/// plaintext wins unless an encrypted copy exists and the plaintext does not match the manifest
+/// hash `recorded`, i.e. it is a stale copy from before the input was re-fetched encrypted.
+fn read_cached_input(
+    plain: &Path,
+    encrypted: &Path,
+    recorded: Option<&str>,
+    key: impl FnOnce() -> Result<Key>,
+) -> Result<Option<Input>> {
+    if plain.exists() {
+        let input = Input::load(plain)?;
+        let stale = encrypted.exists() && recorded.is_some_and(|hash| sha256_hex(&input) != hash);
+        if !stale {
+            return Ok(Some(input));
+        }
+        tracing::warn!(
+            "ignoring {}, which does not match the manifest; reading {} instead",
+            plain.display(),
+            encrypted.display()
+        );
+    }
+    if !encrypted.exists() {
+        return Ok(None);
+    }
+    Ok(Some(Input::Text(decrypt_input(&fs::read(encrypted)?, &key()?)?)))
+}
+
+/// Inputs at least this big are memory-mapped rather than read into a `String`.
+pub const MMAP_THRESHOLD: u64 = 1 << 20;
+
+/// A day's input text: read into memory, or memory-mapped when it is large (e.g. fan-made
+/// "upping the ante" inputs), so solutions borrow it as a `&str` without copying.
+pub enum Input {
+    Text(String),
+    Mapped(Mmap),
+}
+
+impl Input {
+    /// Load `path`, mapping it if it is at least `MMAP_THRESHOLD` bytes. The text is checked to
+    /// be UTF-8 once, here.
+    pub fn load(path: &Path) -> Result<Input> {
+        let file = fs::File::open(path)?;
+        if file.metadata()?.len() < MMAP_THRESHOLD {
+            return Ok(Input::Text(fs::read_to_string(path)?));
+        }
+        // SAFETY: cached inputs are written once and not modified while a solution runs.
+        let map = unsafe { Mmap::map(&file)? };
+        if let Err(e) = std::str::from_utf8(&map) {
+            bail!("Input {} is not valid UTF-8: {e}", path.display());
+        }
+        Ok(Input::Mapped(map))
+    }
+}
+
+impl std::ops::Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Input::Text(text) => text,
+            // SAFETY: `Input::load` checked the mapped bytes are UTF-8.
+            Input::Mapped(map) => unsafe { std::str::from_utf8_unchecked(map) },
+        }
+    }
+}
+
+/// Is there a cached input for `day`, either plaintext or encrypted?
+pub fn input_cached(day: u8) -> bool {
+    input_path(day).exists() || encrypted_input_path(day).exists()
+}
+
+/// Write `contents` to the input cache for `day`. When `AOC_INPUT_KEY` is set the input is stored
+/// encrypted (`dayXX.txt.enc`) so it can be committed; otherwise it is stored as plain text.
+pub fn cache_input(day: u8, contents: &str) -> Result<PathBuf> {
+    ensure_inputs_dir()?;
+    record_input_hash(day, contents)?;
+    write_input(&input_path(day), contents, input_key()?.as_ref())
+}
+
+/// Write an input to `plain`, or encrypted to `<plain>.enc` with `key`. An encrypted write
+/// removes any older plaintext copy, which `read_or_fetch_input` would otherwise prefer. Parent
+/// directories from an `input_pattern` like `{year}/day{day:02}.txt` are created as needed.
+fn write_input(plain: &Path, contents: &str, key: Option<&Key>) -> Result<PathBuf> {
+    if let Some(parent) = plain.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    match key {
+        Some(key) => {
+            let path = encrypted_path_for(plain);
+            fs::write(&path, encrypt_input(contents, key)?)?;
+            if plain.exists() {
+                fs::remove_file(plain)?;
+            }
+            Ok(path)
+        }
+        None => {
+            fs::write(plain, contents)?;
+            Ok(plain.to_path_buf())
+        }
+    }
+}
+
+// -----------------------------
+// Input integrity
+// -----------------------------
+
+/// Responses AoC serves from the input endpoint that are not puzzle inputs.
+#[cfg_attr(not(feature = "online"), allow(dead_code))]
+const NOT_AN_INPUT: &[&str] = &[
+    "Please don't repeatedly request this endpoint before it unlocks",
+    "Puzzle inputs differ by user",
+    "Please log in",
+];
+
+/// Check that a fetched body is a puzzle input and normalise it: strip a UTF-8 BOM, convert
+/// CRLF/CR line endings to LF and ensure a single trailing newline.
+#[cfg_attr(not(feature = "online"), allow(dead_code))]
+pub fn normalise_input(body: &str) -> Result<String> {
+    let body = body.strip_prefix('\u{feff}').unwrap_or(body);
+    let head = body.trim_start();
+    if head.is_empty() {
+        bail!("Fetched input is empty");
+    }
+    let lower = head.chars().take(9).collect::<String>().to_ascii_lowercase();
+    if lower.starts_with("<!doctype") || lower.starts_with("<html") {
+        bail!("Fetched an HTML page instead of an input (is AOC_SESSION still valid?)");
+    }
+    if let Some(msg) = NOT_AN_INPUT.iter().find(|m| head.starts_with(*m)) {
+        bail!("AoC refused the request: {msg}");
+    }
+    let mut text = body.replace("\r\n", "\n").replace('\r', "\n");
+    text.truncate(text.trim_end_matches('\n').len());
+    text.push('\n');
+    Ok(text)
+}
+
+/// Hex SHA-256 of an input's (plaintext) contents.
+pub fn sha256_hex(contents: &str) -> String {
+    hex::encode(Sha256::digest(contents.as_bytes()))
+}
+
+/// Manifest of cached input hashes, in `sha256sum` format so it can be checked with
+/// `sha256sum -c manifest.sha256` from the inputs directory.
+pub fn manifest_path() -> PathBuf {
+    inputs_dir().join("manifest.sha256")
+}
+
+fn read_manifest() -> Result<BTreeMap<String, String>> {
+    let path = manifest_path();
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    fs::read_to_string(&path)?
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let (hash, name) = l
+                .split_once("  ")
+                .ok_or_else(|| eyre!("Malformed line in {}: {l:?}", path.display()))?;
+            Ok((name.to_string(), hash.to_string()))
+        })
+        .collect()
+}
+
+fn write_manifest(manifest: &BTreeMap<String, String>) -> Result<()> {
+    let text: String = manifest
+        .iter()
+        .map(|(name, hash)| format!("{hash}  {name}\n"))
+        .collect();
+    fs::write(manifest_path(), text)?;
+    Ok(())
+}
+
+/// Record the hash of `day`'s input in the manifest.
+pub fn record_input_hash(day: u8, contents: &str) -> Result<()> {
+    let mut manifest = read_manifest()?;
+    manifest.insert(input_file_name(day), sha256_hex(contents));
+    write_manifest(&manifest)
+}
+
+/// Outcome of comparing an input against its manifest entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputCheck {
+    Verified,
+    /// The manifest has no entry for this day (e.g. the input was added by hand).
+    Unrecorded,
+    Mismatch { expected: String, actual: String },
+}
+
+/// Compare `contents` against the manifest hash recorded for `day`.
+pub fn check_input_hash(day: u8, contents: &str) -> Result<InputCheck> {
+    let Some(expected) = read_manifest()?.remove(&input_file_name(day)) else {
+        return Ok(InputCheck::Unrecorded);
+    };
+    let actual = sha256_hex(contents);
+    if actual == expected {
+        Ok(InputCheck::Verified)
+    } else {
+        Ok(InputCheck::Mismatch { expected, actual })
+    }
+}
+
+// -----------------------------
+// Encrypted input cache
+// -----------------------------
+
+/// Environment variable holding the hex-encoded 32-byte key for the encrypted input cache.
+/// Generate one with `aoc25 inputs keygen`.
+pub const INPUT_KEY_VAR: &str = "AOC_INPUT_KEY";
+
+/// Header identifying an encrypted input file (and its format version).
+const ENCRYPTED_MAGIC: &[u8] = b"AOC25ENC1";
+const NONCE_LEN: usize = 12;
+
+/// The input encryption key from the environment, or `None` if encryption is not configured.
+pub fn input_key() -> Result<Option<Key>> {
+    match std::env::var(INPUT_KEY_VAR) {
+        Ok(hex_key) => parse_key(&hex_key).map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+/// A freshly generated random key, hex-encoded for use as `AOC_INPUT_KEY`.
+pub fn generate_key() -> String {
+    hex::encode(ChaCha20Poly1305::generate_key(&mut OsRng))
+}
+
+fn parse_key(hex_key: &str) -> Result<Key> {
+    let bytes = hex::decode(hex_key.trim())
+        .map_err(|e| eyre!("{INPUT_KEY_VAR} is not valid hex: {e}"))?;
+    if bytes.len() != 32 {
+        bail!(
+            "{INPUT_KEY_VAR} must be 32 bytes (64 hex chars), got {} bytes",
+            bytes.len()
+        );
+    }
+    Ok(*Key::from_slice(&bytes))
+}
+
+/// Encrypt an input with ChaCha20-Poly1305. Layout: magic header, 12-byte nonce, ciphertext.
+pub fn encrypt_input(plaintext: &str, key: &Key) -> Result<Vec<u8>> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| eyre!("Failed to encrypt input"))?;
+    let mut out = Vec::with_capacity(ENCRYPTED_MAGIC.len() + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(ENCRYPTED_MAGIC);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt an input produced by `encrypt_input`. Fails on a wrong key or a tampered file.
+pub fn decrypt_input(data: &[u8], key: &Key) -> Result<String> {
+    let body = data
+        .strip_prefix(ENCRYPTED_MAGIC)
+        .ok_or_else(|| eyre!("Not an encrypted input file (missing header)"))?;
+    if body.len() < NONCE_LEN {
+        bail!("Encrypted input is truncated");
+    }
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    let plaintext = ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| eyre!("Failed to decrypt input: wrong {INPUT_KEY_VAR} or corrupted file"))?;
+    Ok(String::from_utf8(plaintext)?)
+}
+
+/// The encrypted counterpart of a plaintext input path: `dayXX.txt` -> `dayXX.txt.enc`.
+pub fn encrypted_path_for(plain: &Path) -> PathBuf {
+    let mut name = plain.as_os_str().to_owned();
+    name.push(".enc");
+    PathBuf::from(name)
+}
+
+/// Encrypt the plaintext input at `plain` to `<plain>.enc`, removing the original unless `keep`.
+pub fn encrypt_file(plain: &Path, key: &Key, keep: bool) -> Result<PathBuf> {
+    let target = encrypted_path_for(plain);
+    fs::write(&target, encrypt_input(&fs::read_to_string(plain)?, key)?)?;
+    if !keep {
+        fs::remove_file(plain)?;
+    }
+    Ok(target)
+}
+
+/// Decrypt `<plain>.enc` back to `plain`, removing the encrypted copy unless `keep`.
+pub fn decrypt_file(plain: &Path, key: &Key, keep: bool) -> Result<PathBuf> {
+    let source = encrypted_path_for(plain);
+    fs::write(plain, decrypt_input(&fs::read(&source)?, key)?)?;
+    if !keep {
+        fs::remove_file(&source)?;
+    }
+    Ok(plain.to_path_buf())
+}
+
 #[cfg(feature = "online")]
 fn session_cookie() -> Result<String> {
     let sess = std::env::var("AOC_SESSION").map_err(|_| eyre!(
@@ -81,7 +511,7 @@ fn session_cookie() -> Result<String> {
 #[cfg(feature = "online")]
 fn client() -> Result<reqwest::blocking::Client> {
     let client = reqwest::blocking::Client::builder()
-        .user_agent("aoc25-rust (https://github.com/craigjperry2)")
+        .user_agent(&config::current().user_agent)
         .build()?;
     Ok(client)
 }
@@ -93,10 +523,12 @@ pub fn fetch_input(day: u8) -> Result<String> {
         .get(url)
         .header(reqwest::header::COOKIE, session_cookie()?)
         .send()?;
-    if !resp.status().is_success() {
-        bail!("Failed to fetch input: HTTP {}", resp.status());
+    let status = resp.status();
+    let body = resp.text()?;
+    if !status.is_success() {
+        bail!("Failed to fetch input: HTTP {status}: {}", body.lines().next().unwrap_or(""));
     }
-    Ok(resp.text()?)
+    normalise_input(&body)
 }
 
 #[cfg(not(feature = "online"))]
@@ -136,8 +568,130 @@ mod tests {
         assert_eq!(input_path(12), Path::new("inputs/day12.txt"));
     }
 
+    #[test]
+    fn encrypted_path_appends_suffix() {
+        assert_eq!(encrypted_input_path(3), Path::new("inputs/day03.txt.enc"));
+    }
+
+    #[test]
+    fn large_inputs_are_mapped() {
+        let dir = tempfile::tempdir().unwrap();
+        let small = dir.path().join("small.txt");
+        let large = dir.path().join("large.txt");
+        fs::write(&small, "1 2\n").unwrap();
+        fs::write(&large, "123\n".repeat(MMAP_THRESHOLD as usize / 4)).unwrap();
+        assert!(matches!(Input::load(&small).unwrap(), Input::Text(_)));
+        let input = Input::load(&large).unwrap();
+        assert!(matches!(input, Input::Mapped(_)));
+        assert_eq!(input.lines().count(), MMAP_THRESHOLD as usize / 4);
+        drop(input);
+        fs::write(&large, vec![0xff; MMAP_THRESHOLD as usize]).unwrap();
+        assert!(Input::load(&large).is_err());
+    }
+
+    #[test]
+    fn encrypted_write_replaces_plaintext() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("day01.txt");
+        let key = parse_key(&generate_key()).unwrap();
+        write_input(&plain, "old\n", None).unwrap();
+        let encrypted = write_input(&plain, "new\n", Some(&key)).unwrap();
+        assert!(!plain.exists());
+        assert_eq!(decrypt_input(&fs::read(&encrypted).unwrap(), &key).unwrap(), "new\n");
+        write_inputs_gitignore(dir.path()).unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join(".gitignore")).unwrap(), INPUTS_GITIGNORE);
+    }
+
+    #[test]
+    fn stale_plaintext_yields_to_the_encrypted_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("day01.txt");
+        let encrypted = encrypted_path_for(&plain);
+        let key = parse_key(&generate_key()).unwrap();
+        let read = |recorded: Option<&str>| {
+            let input = read_cached_input(&plain, &encrypted, recorded, || Ok(key)).unwrap();
+            input.map(|input| input.to_string())
+        };
+        assert_eq!(read(None), None);
+        write_input(&plain, "new\n", Some(&key)).unwrap();
+        fs::write(&plain, "old\n").unwrap();
+        assert_eq!(read(Some(&sha256_hex("new\n"))).as_deref(), Some("new\n"));
+        assert_eq!(read(Some(&sha256_hex("old\n"))).as_deref(), Some("old\n"));
+        assert_eq!(read(None).as_deref(), Some("old\n"));
+    }
+
+    #[test]
+    fn patterned_inputs_get_their_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config::Config {
+            input_pattern: "{year}/day{day:02}.txt".to_string(),
+            ..config::Config::default()
+        };
+        let plain = dir.path().join(config.input_file_name(7));
+        assert_eq!(write_input(&plain, "7\n", None).unwrap(), dir.path().join("2025/day07.txt"));
+        assert_eq!(fs::read_to_string(&plain).unwrap(), "7\n");
+    }
+
+    #[test]
+    fn encrypt_decrypt_round_trip() {
+        let key = parse_key(&generate_key()).unwrap();
+        let data = encrypt_input("1 2 3\n4 5 6\n", &key).unwrap();
+        assert!(data.starts_with(ENCRYPTED_MAGIC));
+        assert_eq!(decrypt_input(&data, &key).unwrap(), "1 2 3\n4 5 6\n");
+    }
+
+    #[test]
+    fn decrypt_rejects_wrong_key() {
+        let key = parse_key(&generate_key()).unwrap();
+        let other = parse_key(&generate_key()).unwrap();
+        let data = encrypt_input("secret", &key).unwrap();
+        assert!(decrypt_input(&data, &other).is_err());
+    }
+
+    #[test]
+    fn parse_key_validates_length() {
+        assert!(parse_key("abcd").is_err());
+        assert!(parse_key("zz").is_err());
+    }
+
+    #[test]
+    fn normalise_converts_line_endings() {
+        assert_eq!(normalise_input("1\r\n2\r\n").unwrap(), "1\n2\n");
+        assert_eq!(normalise_input("\u{feff}a\nb").unwrap(), "a\nb\n");
+        assert_eq!(normalise_input("a\n\n\n").unwrap(), "a\n");
+        let wide = "€".repeat(100);
+        assert_eq!(normalise_input(&wide).unwrap(), wide + "\n");
+    }
+
+    #[test]
+    fn normalise_rejects_non_inputs() {
+        assert!(normalise_input("").is_err());
+        assert!(normalise_input("<!DOCTYPE html>\n<html>login</html>").is_err());
+        assert!(
+            normalise_input("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n")
+                .is_err()
+        );
+        assert!(normalise_input("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n").is_err());
+    }
+
+    #[test]
+    fn sha256_is_hex_digest() {
+        assert_eq!(
+            sha256_hex("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
     #[test]
     fn day1_is_registered() {
         assert!(solution_for(1).is_some());
     }
+
+    #[test]
+    fn parse_errors_name_the_day() {
+        let solution = solution_for(1).unwrap();
+        let err = run_part(solution, 1, 2, "L5\nR1x\n").unwrap_err();
+        let report = format!("{err:?}");
+        assert!(report.contains("day 1, section 2, line 2, column 2"), "{report}");
+    }
 }
@@ -1,12 +1,12 @@
-use crate::core::Solution;
-use crate::parsers::{lines, parse_day, parse_day_with_preview};
-use color_eyre::eyre::{Result};
+use crate::core::{Example, Solution};
+use crate::parsers::{ParseError, lines, try_parse_text, try_parse_text_with_preview};
+use color_eyre::eyre::Result;
 
 pub struct Day01;
 
 impl Solution for Day01 {
     fn part1(&self, input: &str) -> Result<String> {
-        let rotations = parse_day_with_preview(1, parse_rotation, lines, 5)?;
+        let rotations = try_parse_text_with_preview(input, parse_rotation, lines)?;
         let pointing_at = rotations.iter().scan(50, |state, x| {
             *state += x;
             Some(state.rem_euclid(100))
@@ -16,7 +16,7 @@ impl Solution for Day01 {
     }
 
     fn part2(&self, input: &str) -> Result<String> {
-        let rotations = parse_day(1, parse_rotation)?;
+        let rotations = try_parse_text(input, parse_rotation, lines)?;
         let mut zeros = 0;
         let mut position = 50;
         let dial = 100;
@@ -28,9 +28,24 @@ impl Solution for Day01 {
         }
         Ok(zeros.to_string())
     }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n",
+            part1: Some("3"),
+            part2: Some("6"),
+        }]
+    }
 }
 
-fn parse_rotation(line: &str) -> i32 {
-    let n: i32 = line[1..line.len()].parse().unwrap(); // strip leading L/R and trailing '\n'
-    if line[0..1] == *"L" { -n } else { n }
+/// `L68` turns the dial left (negative) by 68 clicks, `R48` right by 48.
+fn parse_rotation(line: &str) -> Result<i32, ParseError> {
+    let split = line.chars().next().map_or(0, char::len_utf8);
+    let (direction, clicks) = line.split_at(split);
+    let n: i32 = ParseError::parse_field(line, clicks, "a number of clicks")?;
+    match direction {
+        "L" => Ok(-n),
+        "R" => Ok(n),
+        _ => Err(ParseError::at(line, direction, "expected L or R")),
+    }
 }
@@ -1,5 +1,5 @@
-use crate::core::Solution;
-use crate::parsers::{parse_day_with_preview, positive_ints};
+use crate::core::{Example, Solution};
+use crate::parsers::{parse_text_with_preview, positive_ints, repeat_digits};
 use color_eyre::eyre::Result;
 
 pub struct Day02;
@@ -7,17 +7,26 @@ pub struct Day02;
 impl Solution for Day02 {
     fn part1(&self, input: &str) -> Result<String> {
         let products =
-            parse_day_with_preview(2, positive_ints, |text| text.split(",").collect(), 5)?;
+            parse_text_with_preview(input, positive_ints, |text| text.split(",").collect());
         let id_ranges: Vec<(u64, u64)> = products.iter().map(|p| (p[0], p[1])).collect();
         Ok(invalids(&id_ranges).iter().sum::<u64>().to_string())
     }
 
     fn part2(&self, input: &str) -> Result<String> {
         let products =
-            parse_day_with_preview(2, positive_ints, |text| text.split(",").collect(), 5)?;
+            parse_text_with_preview(input, positive_ints, |text| text.split(",").collect());
         let id_ranges: Vec<(u64, u64)> = products.iter().map(|p| (p[0], p[1])).collect();
         Ok(all_invalids(&id_ranges).iter().sum::<u64>().to_string())
     }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,\
+                    446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124",
+            part1: Some("1227775554"),
+            part2: Some("4174379265"),
+        }]
+    }
 }
 
 use std::collections::HashSet;
@@ -36,14 +45,7 @@ fn invalids_in_range(lo: u64, hi: u64, repeat: usize) -> impl Iterator<Item = u6
     };
 
     (first_half..)
-        .map(move |i| {
-            let s = i.to_string();
-            let mut repeated = String::with_capacity(s.len() * repeat);
-            for _ in 0..repeat {
-                repeated.push_str(&s);
-            }
-            repeated.parse::<u64>().unwrap()
-        })
+        .map_while(move |i| repeat_digits(i, repeat as u32))
         .take_while(move |&id| id <= hi)
         .filter(move |&id| id >= lo)
 }
@@ -1,19 +1,27 @@
-use crate::core::Solution;
-use crate::parsers::{lines, parse_day_with_preview};
+use crate::core::{Example, Solution};
+use crate::parsers::{lines, parse_text_with_preview};
 use color_eyre::eyre::Result;
 
 pub struct Day03;
 
 impl Solution for Day03 {
     fn part1(&self, input: &str) -> Result<String> {
-        let banks = parse_day_with_preview(3, |x| x.to_string(), lines, 5)?;
+        let banks = parse_text_with_preview(input, |x| x.to_string(), lines);
         Ok(total_joltage(&banks, 2).to_string())
     }
 
     fn part2(&self, input: &str) -> Result<String> {
-        let banks = parse_day_with_preview(3, |x| x.to_string(), lines, 5)?;
+        let banks = parse_text_with_preview(input, |x| x.to_string(), lines);
         Ok(total_joltage(&banks, 12).to_string())
     }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: "987654321111111\n811111111111119\n234234234234278\n818181911112111\n",
+            part1: Some("357"),
+            part2: Some("3121910778619"),
+        }]
+    }
 }
 
 pub fn joltage(bank: &str, n: usize) -> String {
@@ -1,4 +1,4 @@
-use crate::core::Solution;
+use crate::core::{Example, Solution};
 use crate::parsers::{Grid, MissingChar, Point, DIRECTIONS8};
 use color_eyre::eyre::Result;
 
@@ -6,27 +6,32 @@ pub struct Day04;
 
 impl Solution for Day04 {
     fn part1(&self, input: &str) -> Result<String> {
-        let paper_grid = Grid::from_str_with(
-            &input,
-            &DIRECTIONS8,
-            &Default::default(),
-            MissingChar::Error,
-        );
-        let rolls = accessible_rolls(paper_grid);
+        let paper_grid =
+            Grid::from_str_with(input, &DIRECTIONS8, &Default::default(), MissingChar::Error);
+        let rolls = accessible_rolls(&paper_grid);
         Ok(rolls.len().to_string())
     }
 
     fn part2(&self, input: &str) -> Result<String> {
         let paper_grid =
             Grid::from_str_with(input, &DIRECTIONS8, &Default::default(), MissingChar::Error);
-        let rolls = removable_rolls(paper_grid);
+        let rolls = removable_rolls(&paper_grid);
         Ok(rolls.len().to_string())
     }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n\
+                    .@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.",
+            part1: Some("13"),
+            part2: Some("43"),
+        }]
+    }
 }
 
 /// A roll of paper is accessible if there are fewer than
 /// four rolls of paper in the eight adjacent positions.
-fn is_accessible(point: Point, grid: Grid) -> bool {
+fn is_accessible(point: Point, grid: &Grid) -> bool {
     grid.get(point).unwrap() == '@'
         && grid
             .neighbour_contents(point)
@@ -37,23 +42,15 @@ fn is_accessible(point: Point, grid: Grid) -> bool {
 }
 
 /// The positions of all the accessible rolls of paper on the grid.
-fn accessible_rolls(grid: Grid) -> Vec<Point> {
+fn accessible_rolls(grid: &Grid) -> Vec<Point> {
     grid.find_all(['@'])
         .into_iter()
-        .filter(|&p| is_accessible(p, grid.copy()))
+        .filter(|&p| is_accessible(p, grid))
         .collect()
 }
 
-fn removable_rolls(grid: Grid) -> Vec<Point> {
-    let mut grid2 = grid.copy();
-    let mut q = grid.find_all(['@']);
-    let mut removed = Vec::new();
-    while let Some(p) = q.pop() {
-        if is_accessible(p, grid2.copy()) {
-            removed.push(p);
-            grid2.set(p, '.');
-            q.extend(grid2.neighbours(p));
-        }
-    }
-    removed
+/// All the rolls that can be removed by repeatedly removing the accessible ones.
+fn removable_rolls(grid: &Grid) -> Vec<Point> {
+    let mut grid = grid.copy();
+    grid.peel(|g, p| is_accessible(p, g), '.').concat()
 }
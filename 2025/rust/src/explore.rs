@@ -0,0 +1,257 @@
+//! `explore <day>`: an interactive REPL for poking at a day's input with the `parsers` helpers
+//! before writing a real parser.
+
+use crate::core;
+use crate::parsers::{self, Grid};
+use color_eyre::eyre::{Result, bail, eyre};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Write};
+
+/// REPL commands with their usage, used for `help` and tab completion.
+const COMMANDS: &[(&str, &str)] = &[
+    ("lines", "lines [n]            first n lines (default 10) and the line count"),
+    ("paragraphs", "paragraphs [n]       first n blank-line separated paragraphs"),
+    ("ints", "ints [n]             integers found on the first n lines"),
+    ("words", "words [n]            alphabetic words on the first n lines"),
+    ("atoms", "atoms [n]            atoms (numbers or identifiers) on the first n lines"),
+    ("grid", "grid                 grid size and character frequencies"),
+    ("find", "find <chars>         grid points holding any of <chars>"),
+    ("neighbours", "neighbours x y       neighbours of (x, y) and their contents"),
+    ("show", "show rows [y0 [y1]]  render grid rows y0..y1"),
+    ("help", "help                 this list"),
+    ("quit", "quit                 leave the REPL (also Ctrl-D)"),
+];
+
+const DEFAULT_SHOW: usize = 10;
+
+/// The loaded input plus lazily useful views of it.
+pub struct Session {
+    input: String,
+    grid: Grid,
+}
+
+impl Session {
+    pub fn new(input: String) -> Self {
+        let grid = Grid::from_str(input.trim_end());
+        Session { input, grid }
+    }
+
+    /// Evaluate one REPL command and return its output.
+    pub fn eval(&self, line: &str) -> Result<String> {
+        let args: Vec<&str> = line.split_whitespace().collect();
+        let Some((&cmd, rest)) = args.split_first() else {
+            return Ok(String::new());
+        };
+        match cmd {
+            "lines" => Ok(preview("lines", &parsers::lines(&self.input), count_arg(rest)?)),
+            "paragraphs" => Ok(preview(
+                "paragraphs",
+                &parsers::paragraphs(self.input.trim_end()),
+                count_arg(rest)?,
+            )),
+            "ints" => Ok(self.per_line("ints", parsers::ints, count_arg(rest)?)),
+            "words" => Ok(self.per_line("words", parsers::words, count_arg(rest)?)),
+            "atoms" => Ok(self.per_line("atoms", parsers::atoms, count_arg(rest)?)),
+            "grid" => Ok(self.grid_summary()),
+            "find" => {
+                let chars: String = rest.concat();
+                if chars.is_empty() {
+                    bail!("usage: find <chars>");
+                }
+                let mut points = self.grid.find_all(chars.chars());
+                points.sort_by_key(|&(x, y)| (y, x));
+                let mut out = format!("{} points\n", points.len());
+                for p in points.iter().take(DEFAULT_SHOW * 2) {
+                    writeln!(out, "  {p:?} {:?}", self.grid.get(*p).unwrap_or(' '))?;
+                }
+                Ok(out)
+            }
+            "neighbours" | "neighbors" => {
+                let [x, y] = rest else {
+                    bail!("usage: neighbours x y");
+                };
+                let p = (x.parse()?, y.parse()?);
+                let mut out = format!("{p:?} {:?}\n", self.grid.get(p));
+                for q in self.grid.neighbours(p) {
+                    writeln!(out, "  {q:?} {:?}", self.grid.get(q).unwrap_or(' '))?;
+                }
+                Ok(out)
+            }
+            "show" => match rest {
+                ["rows", range @ ..] => {
+                    let y0 = range.first().map(|s| s.parse()).transpose()?.unwrap_or(0);
+                    let y1 = range
+                        .get(1)
+                        .map(|s| s.parse())
+                        .transpose()?
+                        .unwrap_or(self.grid.size.1);
+                    Ok(self
+                        .grid
+                        .to_rows(None, Some(y0..y1))
+                        .into_iter()
+                        .zip(y0..)
+                        .map(|(row, y)| format!("{y:>4} {}\n", row.into_iter().collect::<String>()))
+                        .collect())
+                }
+                _ => bail!("usage: show rows [y0 [y1]]"),
+            },
+            "help" => Ok(COMMANDS.iter().map(|(_, usage)| format!("{usage}\n")).collect()),
+            _ => Err(eyre!("unknown command {cmd:?}; try `help`")),
+        }
+    }
+
+    fn per_line<T: Debug>(&self, title: &str, parser: fn(&str) -> Vec<T>, n: usize) -> String {
+        let lines = parsers::lines(&self.input);
+        let mut out = String::new();
+        let total: usize = lines.iter().map(|l| parser(l).len()).sum();
+        let _ = writeln!(out, "{total} {title} over {} lines", lines.len());
+        for (i, line) in lines.iter().take(n).enumerate() {
+            let _ = writeln!(out, "{:>4} {:?}", i + 1, parser(line));
+        }
+        out
+    }
+
+    fn grid_summary(&self) -> String {
+        let mut freq: BTreeMap<char, usize> = BTreeMap::new();
+        for row in self.grid.to_rows(None, None) {
+            for ch in row {
+                *freq.entry(ch).or_default() += 1;
+            }
+        }
+        let mut out = format!("size {}x{}\n", self.grid.size.0, self.grid.size.1);
+        for (ch, n) in freq {
+            let _ = writeln!(out, "  {ch:?} x {n}");
+        }
+        out
+    }
+}
+
+fn count_arg(rest: &[&str]) -> Result<usize> {
+    match rest {
+        [] => Ok(DEFAULT_SHOW),
+        [n] => Ok(n.parse()?),
+        _ => bail!("expected at most one count argument"),
+    }
+}
+
+fn preview(title: &str, items: &[&str], n: usize) -> String {
+    let mut out = format!("{} {title}\n", items.len());
+    for (i, item) in items.iter().take(n).enumerate() {
+        let _ = writeln!(out, "{:>4} {item}", i + 1);
+    }
+    if items.len() > n {
+        let _ = writeln!(out, "... and {} more", items.len() - n);
+    }
+    out
+}
+
+/// Tab completion of command names (and `rows` after `show`).
+struct ExploreHelper;
+
+impl Completer for ExploreHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let head = &line[..pos];
+        let start = head.rfind(' ').map_or(0, |i| i + 1);
+        let word = &head[start..];
+        let options: Vec<&str> = if start == 0 {
+            COMMANDS.iter().map(|(name, _)| *name).collect()
+        } else if head.trim_start().starts_with("show ") {
+            vec!["rows"]
+        } else {
+            vec![]
+        };
+        let candidates = options
+            .into_iter()
+            .filter(|o| o.starts_with(word))
+            .map(|o| Pair {
+                display: o.to_string(),
+                replacement: format!("{o} "),
+            })
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ExploreHelper {
+    type Hint = String;
+}
+impl Highlighter for ExploreHelper {}
+impl Validator for ExploreHelper {}
+impl Helper for ExploreHelper {}
+
+/// Load the input for `day` and run the REPL until `quit` or end of input.
+pub fn explore(day: u8) -> Result<()> {
+    let session = Session::new(core::read_or_fetch_input(day)?.to_string());
+    let history = core::inputs_dir().join(".explore_history");
+    let mut editor: Editor<ExploreHelper, _> = Editor::new()?;
+    editor.set_helper(Some(ExploreHelper));
+    let _ = editor.load_history(&history);
+
+    println!("Exploring day {day}. Type `help` for commands, Tab to complete.");
+    loop {
+        let line = match editor.readline(&format!("day{day:02}> ")) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+        if matches!(line, "quit" | "exit" | "q") {
+            break;
+        }
+        match session.eval(line) {
+            Ok(out) => print!("{out}"),
+            Err(e) => eprintln!("error: {e}"),
+        }
+    }
+    if core::ensure_inputs_dir().is_ok() {
+        let _ = editor.save_history(&history);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#.1\n.@2\n\nab 3";
+
+    #[test]
+    fn lines_and_ints() {
+        let session = Session::new(EXAMPLE.to_string());
+        assert!(session.eval("lines 2").unwrap().starts_with("4 lines\n"));
+        assert!(session.eval("ints").unwrap().contains("  4 [3]"));
+    }
+
+    #[test]
+    fn grid_queries() {
+        let session = Session::new(EXAMPLE.to_string());
+        assert_eq!(session.eval("find @").unwrap(), "1 points\n  (1, 1) '@'\n");
+        assert!(session.eval("neighbours 1 1").unwrap().contains("(2, 1) '2'"));
+        assert_eq!(session.eval("show rows 0 2").unwrap(), "   0 #.1 \n   1 .@2 \n");
+    }
+
+    #[test]
+    fn unknown_command_is_an_error() {
+        let session = Session::new(EXAMPLE.to_string());
+        assert!(session.eval("frobnicate").is_err());
+        assert!(session.eval("neighbours 1").is_err());
+    }
+}
@@ -0,0 +1,13 @@
+//! The solutions and their toolkit. `parsers` is public so helpers no day uses yet are not dead code.
+
+pub mod cli;
+mod config;
+mod core;
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod explore;
+pub mod parsers;
@@ -0,0 +1,219 @@
+//! Atoms: the numbers and identifiers in a line, parsed without losing precision.
+//!
+//! Integers are tried as `i64`, then `i128`/`u128`, then `BigInt`, so values above 2⁵³ are kept
+//! exactly. Only numbers written with a decimal point become floats, and every atom keeps its
+//! original text:
+//!
+//! ```ignore
+//! let [name, size] = &atoms("disk 18446744073709551616")[..] else { bail!("bad line") };
+//! assert_eq!(name.as_str(), Some("disk"));
+//! assert_eq!(size.as_u128(), Some(1 << 64));
+//! ```
+
+use super::atoms_iter;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use std::fmt;
+
+/// The value of an atom, in the narrowest type that holds it exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AtomValue {
+    Int(i64),
+    I128(i128),
+    U128(u128),
+    Big(BigInt),
+    Float(f64),
+    Str,
+}
+
+/// A parsed atom: its value and the text it was parsed from.
+#[derive(Clone, PartialEq)]
+pub struct Atom {
+    value: AtomValue,
+    lexeme: String,
+}
+
+impl Atom {
+    pub fn value(&self) -> &AtomValue {
+        &self.value
+    }
+
+    /// The original text, e.g. `+007` or `1.50`.
+    pub fn lexeme(&self) -> &str {
+        &self.lexeme
+    }
+
+    pub fn is_number(&self) -> bool {
+        self.value != AtomValue::Str
+    }
+
+    pub fn is_int(&self) -> bool {
+        !matches!(self.value, AtomValue::Float(_) | AtomValue::Str)
+    }
+
+    /// The text of a non-numeric atom.
+    pub fn as_str(&self) -> Option<&str> {
+        (self.value == AtomValue::Str).then_some(self.lexeme.as_str())
+    }
+
+    /// The value of an integer atom that fits in an `i64`.
+    pub fn as_int(&self) -> Option<i64> {
+        match self.value {
+            AtomValue::Int(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// The value of an integer atom that fits in an `i128`.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self.value {
+            AtomValue::Int(n) => Some(n.into()),
+            AtomValue::I128(n) => Some(n),
+            AtomValue::U128(n) => n.try_into().ok(),
+            _ => None,
+        }
+    }
+
+    /// The value of a non-negative integer atom that fits in a `u128`.
+    pub fn as_u128(&self) -> Option<u128> {
+        match self.value {
+            AtomValue::Int(n) => n.try_into().ok(),
+            AtomValue::I128(n) => n.try_into().ok(),
+            AtomValue::U128(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// The value of any integer atom.
+    pub fn as_big(&self) -> Option<BigInt> {
+        match &self.value {
+            AtomValue::Int(n) => Some((*n).into()),
+            AtomValue::I128(n) => Some((*n).into()),
+            AtomValue::U128(n) => Some((*n).into()),
+            AtomValue::Big(n) => Some(n.clone()),
+            _ => None,
+        }
+    }
+
+    /// The value of any numeric atom as an `f64` (rounded if need be).
+    pub fn as_float(&self) -> Option<f64> {
+        match &self.value {
+            AtomValue::Int(n) => Some(*n as f64),
+            AtomValue::I128(n) => Some(*n as f64),
+            AtomValue::U128(n) => Some(*n as f64),
+            AtomValue::Big(n) => n.to_f64(),
+            AtomValue::Float(x) => Some(*x),
+            AtomValue::Str => None,
+        }
+    }
+}
+
+impl fmt::Debug for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            AtomValue::Str => write!(f, "Str({:?})", self.lexeme),
+            value => write!(f, "{value:?}"),
+        }
+    }
+}
+
+impl fmt::Display for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.lexeme)
+    }
+}
+
+/// Is `t` an optionally signed run of digits, with at most one `.` followed by more digits?
+fn numeric_kind(t: &str) -> Option<bool> {
+    let unsigned = t.strip_prefix(['+', '-']).unwrap_or(t);
+    let (whole, fraction) = match unsigned.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (unsigned, None),
+    };
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    match fraction {
+        None if all_digits(whole) => Some(false),
+        Some(fraction) if all_digits(whole) && all_digits(fraction) => Some(true),
+        _ => None,
+    }
+}
+
+/// Parse `text` into a single `Atom` (an integer, a decimal or a string). Trims whitespace.
+pub fn atom(text: &str) -> Atom {
+    let t = text.trim();
+    let value = match numeric_kind(t) {
+        None => AtomValue::Str,
+        Some(true) => AtomValue::Float(t.parse().expect("decimal digits parse as f64")),
+        Some(false) => {
+            if let Ok(n) = t.parse() {
+                AtomValue::Int(n)
+            } else if let Ok(n) = t.parse() {
+                AtomValue::I128(n)
+            } else if let Ok(n) = t.parse() {
+                AtomValue::U128(n)
+            } else {
+                AtomValue::Big(t.parse().expect("digits parse as BigInt"))
+            }
+        }
+    };
+    Atom {
+        value,
+        lexeme: t.to_string(),
+    }
+}
+
+/// All atoms (numbers or identifiers) in `text`. Skips punctuation.
+pub fn atoms(text: &str) -> Vec<Atom> {
+    atoms_iter(text).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_keep_their_precision() {
+        let text = [
+            "9007199254740993",
+            "-170141183460469231731687303715884105728",
+            "340282366920938463463374607431768211455",
+            "340282366920938463463374607431768211456",
+        ]
+        .join(" ");
+        let values: Vec<AtomValue> = atoms(&text)
+            .into_iter()
+            .map(|a| a.value().clone())
+            .collect();
+        assert_eq!(values[0], AtomValue::Int(9_007_199_254_740_993));
+        assert_eq!(values[1], AtomValue::I128(i128::MIN));
+        assert_eq!(values[2], AtomValue::U128(u128::MAX));
+        assert_eq!(
+            values[3],
+            AtomValue::Big("340282366920938463463374607431768211456".parse().unwrap())
+        );
+        let big = atom("+340282366920938463463374607431768211456");
+        assert_eq!(big.as_big(), Some(BigInt::from(u128::MAX) + 1));
+        assert_eq!(big.as_float(), Some(2f64.powi(128)));
+        assert_eq!(atom("-7").as_big(), Some(BigInt::from(-7)));
+    }
+
+    #[test]
+    fn decimals_strings_and_lexemes() {
+        let found = atoms("move +007 to x1 at 2.50 m/s, inf or NaN.");
+        let lexemes: Vec<&str> = found.iter().map(Atom::lexeme).collect();
+        assert_eq!(
+            lexemes,
+            [
+                "move", "+007", "to", "x1", "at", "2.50", "m", "s", "inf", "or", "NaN"
+            ]
+        );
+        assert_eq!(found[1].as_int(), Some(7));
+        assert_eq!(found[3].as_str(), Some("x1"));
+        assert_eq!(found[5].value(), &AtomValue::Float(2.5));
+        assert_eq!(found[5].as_int(), None);
+        assert_eq!(found[8].as_float(), None);
+        assert_eq!(atom(" 3.0 ").as_float(), Some(3.0));
+        assert!(!atom("3.0").is_int());
+        assert_eq!(format!("{:?}", atoms("a 1")), "[Str(\"a\"), Int(1)]");
+    }
+}
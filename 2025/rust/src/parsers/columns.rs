@@ -0,0 +1,192 @@
+//! Column-aligned (fixed-width) text: worksheets, crate stacks and other tables laid out in
+//! columns of characters rather than separated by delimiters.
+//!
+//! A `ColumnLayout` finds the column spans, either from the all-space gutters between them or
+//! from a marker line whose non-space characters start each column. Splitting lines with it gives
+//! a `Table` of cell slices, whose numbers can be read across (`read_horizontally`) or down
+//! (`read_vertically`):
+//!
+//! ```ignore
+//! // 2025 day 6 part 2: each problem is a column, its numbers written top to bottom.
+//! let table = ColumnLayout::from_blank_columns(&lines).split(&lines);
+//! for problem in table.columns() {
+//!     let (op, digits) = problem.split_last().unwrap();
+//!     let numbers = read_vertically(digits);
+//! }
+//! ```
+//!
+//! Columns are byte offsets, which is fine for AoC's ASCII inputs. Lines shorter than a column
+//! give a short or empty cell rather than an error.
+
+use super::stream::ints_iter;
+use std::ops::Range;
+
+/// The byte spans of a table's columns, left to right.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnLayout {
+    spans: Vec<Range<usize>>,
+}
+
+impl ColumnLayout {
+    pub fn new(spans: Vec<Range<usize>>) -> Self {
+        ColumnLayout { spans }
+    }
+
+    /// Columns are the maximal runs of positions where some line has a non-space character.
+    pub fn from_blank_columns(lines: &[&str]) -> Self {
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let mut used = vec![false; width];
+        for line in lines {
+            for (i, b) in line.bytes().enumerate() {
+                used[i] |= b != b' ';
+            }
+        }
+        let mut spans = Vec::new();
+        let mut start = None;
+        for (i, &u) in used.iter().chain([&false]).enumerate() {
+            match (u, start) {
+                (true, None) => start = Some(i),
+                (false, Some(s)) => {
+                    spans.push(s..i);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        ColumnLayout { spans }
+    }
+
+    /// Each run of non-space characters in `marker` (e.g. the operators under a worksheet, or a
+    /// `---- ---` rule) starts a column, which runs up to the one-character gutter before the
+    /// next. The last column runs to `width`.
+    pub fn from_marker_line(marker: &str, width: usize) -> Self {
+        let bytes = marker.as_bytes();
+        let starts: Vec<usize> = (0..bytes.len())
+            .filter(|&i| bytes[i] != b' ' && (i == 0 || bytes[i - 1] == b' '))
+            .collect();
+        let ends = starts
+            .iter()
+            .skip(1)
+            .map(|&next| next - 1)
+            .chain([width.max(bytes.len())]);
+        ColumnLayout {
+            spans: starts.iter().zip(ends).map(|(&s, e)| s..e).collect(),
+        }
+    }
+
+    pub fn spans(&self) -> &[Range<usize>] {
+        &self.spans
+    }
+
+    /// The cells of one line, one per column.
+    pub fn split_line<'a>(&self, line: &'a str) -> Vec<&'a str> {
+        let len = line.len();
+        self.spans
+            .iter()
+            .map(|span| {
+                line.get(span.start.min(len)..span.end.min(len))
+                    .unwrap_or("")
+            })
+            .collect()
+    }
+
+    pub fn split<'a>(&self, lines: &[&'a str]) -> Table<'a> {
+        Table {
+            rows: lines.iter().map(|line| self.split_line(line)).collect(),
+        }
+    }
+}
+
+/// Split `text` into a table at its all-space gutters.
+pub fn column_table(text: &str) -> Table<'_> {
+    let lines: Vec<&str> = text.lines().collect();
+    ColumnLayout::from_blank_columns(&lines).split(&lines)
+}
+
+/// Rows of cells sliced from column-aligned lines. Cells keep their padding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table<'a> {
+    rows: Vec<Vec<&'a str>>,
+}
+
+impl<'a> Table<'a> {
+    pub fn rows(&self) -> &[Vec<&'a str>] {
+        &self.rows
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    pub fn cell(&self, row: usize, column: usize) -> &'a str {
+        self.rows[row][column]
+    }
+
+    /// The cells of one column, top to bottom.
+    pub fn column(&self, column: usize) -> Vec<&'a str> {
+        self.rows.iter().map(|row| row[column]).collect()
+    }
+
+    /// All columns, left to right.
+    pub fn columns(&self) -> Vec<Vec<&'a str>> {
+        (0..self.width()).map(|c| self.column(c)).collect()
+    }
+}
+
+/// The integers written across `cells`, in order.
+pub fn read_horizontally(cells: &[&str]) -> Vec<i64> {
+    cells.iter().flat_map(|cell| ints_iter(cell)).collect()
+}
+
+/// The numbers written downwards through `cells`: each character position, left to right, reads
+/// its digits top to bottom. Positions without digits are skipped.
+pub fn read_vertically(cells: &[&str]) -> Vec<u64> {
+    let width = cells.iter().map(|cell| cell.len()).max().unwrap_or(0);
+    (0..width)
+        .filter_map(|i| {
+            let mut digits = cells
+                .iter()
+                .filter_map(|cell| cell.as_bytes().get(i).filter(|b| b.is_ascii_digit()))
+                .peekable();
+            digits.peek()?;
+            Some(digits.fold(0, |n, &d| n * 10 + u64::from(d - b'0')))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSHEET: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
+
+    #[test]
+    fn gutters_and_markers_agree() {
+        let lines: Vec<&str> = WORKSHEET.lines().collect();
+        let by_gutters = ColumnLayout::from_blank_columns(&lines);
+        let by_marker = ColumnLayout::from_marker_line(lines[3], 15);
+        assert_eq!(by_gutters.spans(), [0..3, 4..7, 8..11, 12..15]);
+        assert_eq!(by_gutters, by_marker);
+
+        let table = by_gutters.split(&lines);
+        assert_eq!((table.height(), table.width()), (4, 4));
+        assert_eq!(table.column(1), ["328", "64 ", "98 ", "+  "]);
+        assert_eq!(read_horizontally(&table.column(2)[..3]), [51, 387, 215]);
+        assert_eq!(read_vertically(&table.column(2)[..3]), [32, 581, 175]);
+        assert_eq!(read_vertically(&["123", " 45", "  6"]), [1, 24, 356]);
+    }
+
+    #[test]
+    fn crate_stacks() {
+        // 2022 day 5: the stacks are every fourth character, some lines cut short.
+        let table = column_table("    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3 ");
+        let tops: Vec<&str> = table.rows()[0].iter().map(|c| c.trim()).collect();
+        assert_eq!(tops, ["", "[D]", ""]);
+        assert_eq!(table.cell(2, 2), "[P]");
+        assert_eq!(read_horizontally(&table.rows()[3]), [1, 2, 3]);
+    }
+}
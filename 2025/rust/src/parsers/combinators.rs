@@ -0,0 +1,424 @@
+//! A small parser-combinator layer for structured lines that the regex scanners can't describe.
+//!
+//! A parser is any `Fn(&str) -> PResult<T>` returning the value and the unconsumed rest of the
+//! input. Combinators build bigger parsers from smaller ones, and `parse_all` runs one over a
+//! whole line or section, turning failures into located `ParseError`s:
+//!
+//! ```ignore
+//! // 2024 day 13: "Button A: X+94, Y+34"
+//! let button = map(
+//!     seq((literal("Button "), word(), literal(": X+"), int(), literal(", Y+"), int())),
+//!     |(_, name, _, x, _, y)| Button { name, x, y },
+//! );
+//! let a: Button = parse_all(line, button)?;
+//! ```
+//!
+//! Combinators do not skip whitespace on their own; wrap a parser in `ws` to allow leading
+//! spaces or line breaks before it.
+
+use super::error::ParseError;
+use std::str::FromStr;
+
+/// Where and why a parser failed: the unconsumed input at that point and what was expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    pub at: &'a str,
+    pub expected: String,
+}
+
+/// A parser's result: the value and the rest of the input, or a failure.
+pub type PResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// Anything that parses a `T` from the front of the input.
+pub trait Parser<'a, T> {
+    fn parse(&self, input: &'a str) -> PResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str) -> PResult<'a, T>,
+{
+    fn parse(&self, input: &'a str) -> PResult<'a, T> {
+        self(input)
+    }
+}
+
+fn fail<'a, T>(at: &'a str, expected: impl Into<String>) -> PResult<'a, T> {
+    Err(Failure {
+        at,
+        expected: expected.into(),
+    })
+}
+
+/// Exactly the text `lit`.
+pub fn literal<'a>(lit: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(lit) {
+        Some(rest) => Ok((&input[..lit.len()], rest)),
+        None => fail(input, format!("{lit:?}")),
+    }
+}
+
+/// The longest non-empty prefix whose characters satisfy `pred`, described as `what` on failure.
+pub fn take_while1<'a>(pred: fn(char) -> bool, what: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let end = input.find(|c| !pred(c)).unwrap_or(input.len());
+        if end == 0 {
+            fail(input, what)
+        } else {
+            Ok((&input[..end], &input[end..]))
+        }
+    }
+}
+
+/// An integer with an optional sign, as any `FromStr` type (`i64`, `u32`, `usize`...).
+pub fn int<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let sign = usize::from(input.starts_with(['-', '+']));
+        let digits = input[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len() - sign);
+        if digits == 0 {
+            return fail(input, "an integer");
+        }
+        let (number, rest) = input.split_at(sign + digits);
+        match number.parse() {
+            Ok(n) => Ok((n, rest)),
+            Err(_) => fail(
+                input,
+                format!("an integer that fits in {}", std::any::type_name::<T>()),
+            ),
+        }
+    }
+}
+
+/// A run of ASCII letters.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1(|c| c.is_ascii_alphabetic(), "a word")
+}
+
+/// `p` after any leading whitespace (including line breaks).
+pub fn ws<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| p.parse(input.trim_start())
+}
+
+/// Apply `f` to the value parsed by `p`.
+pub fn map<'a, A, B>(p: impl Parser<'a, A>, f: impl Fn(A) -> B) -> impl Parser<'a, B> {
+    move |input: &'a str| p.parse(input).map(|(a, rest)| (f(a), rest))
+}
+
+/// `p`, or `None` without consuming anything if it fails.
+pub fn optional<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: &'a str| match p.parse(input) {
+        Ok((t, rest)) => Ok((Some(t), rest)),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+/// Zero or more `p`, stopping at the first failure (or when `p` stops consuming input).
+pub fn many<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: &'a str| {
+        let mut out = Vec::new();
+        while let Ok((t, rest)) = p.parse(input) {
+            if rest.len() == input.len() {
+                break;
+            }
+            out.push(t);
+            input = rest;
+        }
+        Ok((out, input))
+    }
+}
+
+/// Zero or more `p` separated by `sep`, e.g. `sep_by(int(), literal(", "))` for `79, 98`.
+pub fn sep_by<'a, T, S>(p: impl Parser<'a, T>, sep: impl Parser<'a, S>) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let Ok((first, mut rest)) = p.parse(input) else {
+            return Ok((Vec::new(), input));
+        };
+        let mut out = vec![first];
+        while let Ok((_, after_sep)) = sep.parse(rest) {
+            // A separator must be followed by another item.
+            let (t, after) = p.parse(after_sep)?;
+            out.push(t);
+            rest = after;
+        }
+        Ok((out, rest))
+    }
+}
+
+/// `p` between `open` and `close`, keeping only `p`'s value.
+pub fn delimited<'a, O, T, C>(
+    open: impl Parser<'a, O>,
+    p: impl Parser<'a, T>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let (_, rest) = open.parse(input)?;
+        let (t, rest) = p.parse(rest)?;
+        let (_, rest) = close.parse(rest)?;
+        Ok((t, rest))
+    }
+}
+
+/// `p` after `prefix`, keeping only `p`'s value.
+pub fn preceded<'a, P, T>(prefix: impl Parser<'a, P>, p: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let (_, rest) = prefix.parse(input)?;
+        p.parse(rest)
+    }
+}
+
+/// A tuple of parsers run one after another; see `seq`.
+pub trait Sequence<'a, T> {
+    fn parse_seq(&self, input: &'a str) -> PResult<'a, T>;
+}
+
+/// A tuple of alternative parsers tried in order; see `alt`.
+pub trait Choice<'a, T> {
+    fn parse_choice(&self, input: &'a str) -> PResult<'a, T>;
+}
+
+macro_rules! impl_tuples {
+    ($($p:ident $t:ident $i:tt),+) => {
+        impl<'a, $($p: Parser<'a, $t>, $t),+> Sequence<'a, ($($t,)+)> for ($($p,)+) {
+            fn parse_seq(&self, input: &'a str) -> PResult<'a, ($($t,)+)> {
+                let rest = input;
+                $(let ($t, rest) = self.$i.parse(rest)?;)+
+                Ok((($($t,)+), rest))
+            }
+        }
+
+        impl<'a, T, $($p: Parser<'a, T>),+> Choice<'a, T> for ($($p,)+) {
+            fn parse_choice(&self, input: &'a str) -> PResult<'a, T> {
+                let mut best: Option<Failure<'a>> = None;
+                $(
+                    match self.$i.parse(input) {
+                        Ok(done) => return Ok(done),
+                        Err(e) => best = Some(furthest(best, e)),
+                    }
+                )+
+                Err(best.expect("alt has at least one alternative"))
+            }
+        }
+    };
+}
+
+#[allow(non_snake_case)]
+mod tuples {
+    use super::*;
+
+    impl_tuples!(P0 T0 0);
+    impl_tuples!(P0 T0 0, P1 T1 1);
+    impl_tuples!(P0 T0 0, P1 T1 1, P2 T2 2);
+    impl_tuples!(P0 T0 0, P1 T1 1, P2 T2 2, P3 T3 3);
+    impl_tuples!(P0 T0 0, P1 T1 1, P2 T2 2, P3 T3 3, P4 T4 4);
+    impl_tuples!(P0 T0 0, P1 T1 1, P2 T2 2, P3 T3 3, P4 T4 4, P5 T5 5);
+    impl_tuples!(P0 T0 0, P1 T1 1, P2 T2 2, P3 T3 3, P4 T4 4, P5 T5 5, P6 T6 6);
+    impl_tuples!(P0 T0 0, P1 T1 1, P2 T2 2, P3 T3 3, P4 T4 4, P5 T5 5, P6 T6 6, P7 T7 7);
+}
+
+/// The failure that got further into the input, merging expectations at the same position.
+fn furthest<'a>(best: Option<Failure<'a>>, e: Failure<'a>) -> Failure<'a> {
+    match best {
+        None => e,
+        Some(b) if b.at.len() < e.at.len() => b,
+        Some(b) if b.at.len() > e.at.len() => e,
+        Some(b) => Failure {
+            at: b.at,
+            expected: format!("{} or {}", b.expected, e.expected),
+        },
+    }
+}
+
+/// Run a tuple of up to eight parsers in order, giving a tuple of their values.
+pub fn seq<'a, T>(parsers: impl Sequence<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| parsers.parse_seq(input)
+}
+
+/// The first of a tuple of up to eight parsers that succeeds. If all fail, the error is from the
+/// one that got furthest.
+pub fn alt<'a, T>(parsers: impl Choice<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| parsers.parse_choice(input)
+}
+
+/// Parse all of `input` (trailing whitespace allowed) with `p`. Errors point into `input`, so
+/// this composes with `try_parse_text` and section schemas.
+pub fn parse_all<'a, T>(input: &'a str, p: impl Parser<'a, T>) -> Result<T, ParseError> {
+    let (t, rest) = p.parse(input).map_err(|f| failure_to_error(input, f))?;
+    if rest.trim().is_empty() {
+        Ok(t)
+    } else {
+        Err(failure_to_error(
+            input,
+            Failure {
+                at: rest,
+                expected: "end of input".to_string(),
+            },
+        ))
+    }
+}
+
+fn failure_to_error(input: &str, f: Failure<'_>) -> ParseError {
+    // Show the token the parser tripped over: up to the next whitespace, or one character.
+    let token_len =
+        f.at.find(char::is_whitespace)
+            .filter(|&n| n > 0)
+            .or_else(|| f.at.chars().next().map(char::len_utf8))
+            .unwrap_or(0);
+    ParseError::at(
+        input,
+        &f.at[..token_len],
+        format!("expected {}", f.expected),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{lines, paragraphs, try_parse_text};
+
+    #[derive(Debug, PartialEq)]
+    struct Button<'a> {
+        name: &'a str,
+        x: i64,
+        y: i64,
+    }
+
+    fn button<'a>() -> impl Parser<'a, Button<'a>> {
+        map(
+            seq((
+                literal("Button "),
+                word(),
+                literal(": X+"),
+                int(),
+                literal(", Y+"),
+                int(),
+            )),
+            |(_, name, _, x, _, y)| Button { name, x, y },
+        )
+    }
+
+    #[test]
+    fn claw_machine_buttons() {
+        assert_eq!(
+            parse_all("Button A: X+94, Y+34", button()).unwrap(),
+            Button {
+                name: "A",
+                x: 94,
+                y: 34
+            }
+        );
+        let err = parse_all("Button B: X+22, Z+67", button()).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (15, ","));
+        assert_eq!(err.message, "expected \", Y+\"");
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Monkey {
+        id: usize,
+        items: Vec<u64>,
+        operation: (char, Option<u64>),
+        test: (u64, usize, usize),
+    }
+
+    fn monkey(text: &str) -> Result<Monkey, ParseError> {
+        let operand = alt((map(literal("old"), |_| None), map(int(), Some)));
+        let operation = seq((
+            ws(literal("Operation: new = old ")),
+            alt((literal("*"), literal("+"))),
+            literal(" "),
+            operand,
+        ));
+        let test = seq((
+            ws(literal("Test: divisible by ")),
+            int(),
+            ws(literal("If true: throw to monkey ")),
+            int(),
+            ws(literal("If false: throw to monkey ")),
+            int(),
+        ));
+        let monkey = seq((
+            delimited(literal("Monkey "), int(), literal(":")),
+            preceded(
+                ws(literal("Starting items:")),
+                sep_by(ws(int()), literal(",")),
+            ),
+            map(operation, |(_, op, _, n)| (op.chars().next().unwrap(), n)),
+            map(test, |(_, d, _, t, _, f)| (d, t, f)),
+        ));
+        parse_all(
+            text,
+            map(monkey, |(id, items, operation, test)| Monkey {
+                id,
+                items,
+                operation,
+                test,
+            }),
+        )
+    }
+
+    const MONKEYS: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3";
+
+    #[test]
+    fn monkey_notes() {
+        let monkeys = try_parse_text(MONKEYS, monkey, paragraphs).unwrap();
+        assert_eq!(
+            monkeys[0],
+            Monkey {
+                id: 0,
+                items: vec![79, 98],
+                operation: ('*', Some(19)),
+                test: (23, 2, 3),
+            }
+        );
+        assert_eq!(monkeys[1].operation, ('*', None));
+
+        let bad = MONKEYS.replace("old * old", "old / old");
+        let err = parse_sections_err(&bad);
+        assert!(err.contains("section 2, line 10, column 24: expected \"*\" or \"+\""));
+    }
+
+    fn parse_sections_err(text: &str) -> String {
+        format!(
+            "{:?}",
+            try_parse_text(text, monkey, paragraphs).unwrap_err()
+        )
+    }
+
+    #[test]
+    fn small_combinators() {
+        let ints = || sep_by(int::<i32>(), literal(","));
+        assert_eq!(parse_all("1,-2,+3", ints()).unwrap(), [1, -2, 3]);
+        assert!(parse_all("1,2,", ints()).is_err());
+        assert_eq!(parse_all("", ints()).unwrap(), Vec::<i32>::new());
+        assert_eq!(parse_all("abc", many(word())).unwrap(), ["abc"]);
+        assert_eq!(
+            parse_all("x=5", seq((optional(literal("x=")), int::<u8>()))).unwrap(),
+            (Some("x="), 5)
+        );
+        let err = parse_all("300", int::<u8>()).unwrap_err();
+        assert_eq!(err.message, "expected an integer that fits in u8");
+        assert_eq!(
+            try_parse_text(
+                "1 2\n3 x",
+                |l| parse_all(l, sep_by(int::<u8>(), literal(" "))),
+                lines
+            )
+            .unwrap_err()
+            .to_string(),
+            "section 2, line 2, column 3: expected an integer, found \"x\""
+        );
+    }
+}
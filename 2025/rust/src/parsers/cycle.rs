@@ -0,0 +1,178 @@
+//! Cycle detection for simulations that eventually repeat, and fast-forwarding them to a far
+//! step such as 1,000,000,000.
+//!
+//! A simulation is a start state and a step function. Since the state space is finite the
+//! states x₀, x₁, x₂... must repeat: after `prefix` steps they cycle with some `period`.
+//!
+//! - `find_cycle` hashes every state it sees and keeps the history, so any step's state is then a
+//!   lookup; `state_at` stops early if step N comes before the repeat.
+//! - `brent` and `floyd` need only `PartialEq` and constant memory, for states that are expensive
+//!   to hash or store; `Cycle::state_at` then re-simulates at most `prefix + period` steps.
+//!
+//! ```ignore
+//! // 2023 day 14: the platform after a billion spin cycles.
+//! let platform = state_at(grid, |g| spin_cycle(g), 1_000_000_000);
+//! ```
+//!
+//! A walk that can end (a guard leaving the map) fits too: make the state an `Option` whose `None`
+//! steps to itself, and the cycle is the period-1 loop at `None`.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The shape of a repeating sequence: after `prefix` steps, states repeat every `period` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state equals the state at step `n`.
+    pub fn equivalent_step(&self, n: u64) -> usize {
+        let prefix = self.prefix as u64;
+        if n < prefix {
+            n as usize
+        } else {
+            self.prefix + ((n - prefix) % self.period as u64) as usize
+        }
+    }
+
+    /// How many whole periods lie between the end of the prefix and step `n` (0 if `n` is in the
+    /// prefix); handy for extrapolating a quantity that grows by a fixed amount per period.
+    pub fn periods_before(&self, n: u64) -> u64 {
+        n.saturating_sub(self.prefix as u64) / self.period as u64
+    }
+
+    /// The state at step `n`, re-simulating only up to the equivalent early step.
+    pub fn state_at<S>(&self, start: S, mut step: impl FnMut(&S) -> S, n: u64) -> S {
+        (0..self.equivalent_step(n)).fold(start, |s, _| step(&s))
+    }
+}
+
+/// Simulate from `start` until a state repeats, returning the cycle and every state before the
+/// repeat (`history[i]` is the state at step `i`).
+pub fn find_cycle<S: Clone + Eq + Hash>(
+    start: S,
+    mut step: impl FnMut(&S) -> S,
+) -> (Cycle, Vec<S>) {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut history = Vec::new();
+    let mut state = start;
+    loop {
+        if let Some(&prefix) = seen.get(&state) {
+            let period = history.len() - prefix;
+            return (Cycle { prefix, period }, history);
+        }
+        seen.insert(state.clone(), history.len());
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+}
+
+/// The state at step `n`, skipping whole cycles once one is found.
+pub fn state_at<S: Clone + Eq + Hash>(start: S, mut step: impl FnMut(&S) -> S, n: u64) -> S {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut history = Vec::new();
+    let mut state = start;
+    while (history.len() as u64) < n {
+        if let Some(&prefix) = seen.get(&state) {
+            let period = history.len() - prefix;
+            let i = Cycle { prefix, period }.equivalent_step(n);
+            return history.swap_remove(i);
+        }
+        seen.insert(state.clone(), history.len());
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+    state
+}
+
+/// Brent's algorithm: finds the cycle with `O(1)` memory and fewer steps than Floyd's.
+pub fn brent<S: Clone + PartialEq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+    let mut tortoise = start.clone();
+    let mut hare = (0..period).fold(start, |s, _| step(&s));
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+    Cycle { prefix, period }
+}
+
+/// Floyd's tortoise and hare: finds the cycle with `O(1)` memory.
+pub fn floyd<S: Clone + PartialEq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+    let mut prefix = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+    Cycle { prefix, period }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn squares(x: &u64) -> u64 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn all_methods_agree() {
+        for start in [0, 3, 100, 254] {
+            let (cycle, history) = find_cycle(start, squares);
+            assert_eq!(brent(start, squares), cycle);
+            assert_eq!(floyd(start, squares), cycle);
+            assert_eq!(history.len(), cycle.prefix + cycle.period);
+            assert_eq!(squares(history.last().unwrap()), history[cycle.prefix]);
+        }
+        let (cycle, _) = find_cycle(3, squares);
+        assert_eq!((cycle.prefix, cycle.period), (2, 6));
+    }
+
+    #[test]
+    fn fast_forwards() {
+        // 2024 day 14: a robot on an 11x7 wrapping floor comes back every 77 seconds.
+        let robot = |&(x, y): &(i32, i32)| ((x + 2).rem_euclid(11), (y - 3).rem_euclid(7));
+        let (cycle, _) = find_cycle((2, 4), robot);
+        assert_eq!((cycle.prefix, cycle.period), (0, 77));
+        let n = 1_000_000_000;
+        let slow = (0..cycle.equivalent_step(n)).fold((2, 4), |p, _| robot(&p));
+        assert_eq!(state_at((2, 4), robot, n), slow);
+        assert_eq!(cycle.state_at((2, 4), robot, n), slow);
+        assert_eq!(state_at(3, squares, 1), 10);
+        assert_eq!(state_at(3, squares, 1_000_005), state_at(3, squares, 3));
+        assert_eq!(cycle.periods_before(n), n / 77);
+    }
+}
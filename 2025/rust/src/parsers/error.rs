@@ -0,0 +1,269 @@
+//! Fallible parsing with errors that point at the offending input.
+//!
+//! Line parsers return `Result<T, ParseError>`, built with `ParseError::at(input, bad, message)`
+//! where `bad` is a slice of the `input` they were given. `try_parse_text`/`try_parse_day` then
+//! place the error in the whole puzzle input (day, section, line, column) and turn it into a
+//! `color_eyre` report with a snippet of the input:
+//!
+//! ```text
+//! Error:
+//!    0: day 1, section 3, line 3, column 2: expected a number, found "4x"
+//!
+//! Input:
+//!       3 | R4x
+//!         |  ^^
+//! ```
+
+use super::{lines, show_debug_items, show_items};
+use crate::core::read_or_fetch_input;
+use color_eyre::eyre::{Report, Result};
+use color_eyre::{Section, SectionExt};
+use std::fmt::{self, Debug};
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+/// A parse failure located in the input. Boxed to keep `Result<T, ParseError>` small; the
+/// details are reachable as fields through `Deref` (`err.line`, `err.text`...).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError(Box<ParseErrorDetails>);
+
+/// Where and why parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseErrorDetails {
+    pub day: Option<u8>,
+    /// Index of the section (line, paragraph...) being parsed, when parsed via `try_parse_text`.
+    pub section: Option<usize>,
+    /// What the section holds, when parsed via a section schema (e.g. `"ingredient IDs"`).
+    pub section_name: Option<&'static str>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+    pub message: String,
+    /// Address of `text`, so the error can be re-located in any input that contains it.
+    addr: usize,
+    /// The full line containing `text`.
+    source_line: String,
+}
+
+impl Deref for ParseError {
+    type Target = ParseErrorDetails;
+
+    fn deref(&self) -> &ParseErrorDetails {
+        &self.0
+    }
+}
+
+impl DerefMut for ParseError {
+    fn deref_mut(&mut self) -> &mut ParseErrorDetails {
+        &mut self.0
+    }
+}
+
+impl ParseError {
+    /// An error about `bad`, which should be a slice of `input` (the text given to the parser).
+    /// If it is not, the error points at the start of `input`.
+    pub fn at(input: &str, bad: &str, message: impl Into<String>) -> Self {
+        let addr = if offset_in(input, bad.as_ptr() as usize).is_some() {
+            bad.as_ptr() as usize
+        } else {
+            input.as_ptr() as usize
+        };
+        let mut err = ParseError(Box::new(ParseErrorDetails {
+            day: None,
+            section: None,
+            section_name: None,
+            line: 1,
+            column: 1,
+            text: bad.to_string(),
+            message: message.into(),
+            addr,
+            source_line: String::new(),
+        }));
+        err.locate_in(input);
+        err
+    }
+
+    /// Parse `field` (a slice of `input`) as a `T`, describing it as `what` on failure.
+    pub fn parse_field<T: FromStr>(input: &str, field: &str, what: &str) -> Result<T, Self> {
+        field
+            .trim()
+            .parse()
+            .map_err(|_| ParseError::at(input, field, format!("expected {what}")))
+    }
+
+    /// Set `line`, `column` and the snippet relative to `text`, if the error lies within it.
+    fn locate_in(&mut self, text: &str) {
+        let Some(offset) = offset_in(text, self.addr) else {
+            return;
+        };
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        self.line = before.matches('\n').count() + 1;
+        self.column = before[line_start..].chars().count() + 1;
+        self.source_line = text[line_start..].lines().next().unwrap_or("").to_string();
+    }
+
+    /// Re-locate the error relative to `whole`, a larger input containing the offending text.
+    pub(super) fn within(mut self, whole: &str) -> Self {
+        self.locate_in(whole);
+        self
+    }
+
+    pub fn in_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    pub fn in_section(mut self, section: usize) -> Self {
+        self.section = Some(section);
+        self
+    }
+
+    pub fn in_named_section(mut self, section: usize, name: &'static str) -> Self {
+        self.section = Some(section);
+        self.section_name = Some(name);
+        self
+    }
+
+    /// The offending line with the bad text underlined.
+    pub fn snippet(&self) -> String {
+        let width = self.text.chars().count().max(1);
+        let gutter = format!("{:>6} | ", self.line);
+        format!(
+            "{gutter}{}\n{:>9}{}{}",
+            self.source_line,
+            "| ",
+            " ".repeat(self.column - 1),
+            "^".repeat(width)
+        )
+    }
+
+    /// A `color_eyre` report with the snippet attached as an "Input:" section.
+    pub fn report(self) -> Report {
+        let snippet = self.snippet();
+        Report::new(self).section(snippet.header("Input:"))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        match (self.section, self.section_name) {
+            (Some(section), Some(name)) => write!(f, "section {} ({name}), ", section + 1)?,
+            (Some(section), None) => write!(f, "section {}, ", section + 1)?,
+            _ => {}
+        }
+        write!(
+            f,
+            "line {}, column {}: {}, found {:?}",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Byte offset of `addr` in `text`, if it points inside it (or just past its end).
+fn offset_in(text: &str, addr: usize) -> Option<usize> {
+    addr.checked_sub(text.as_ptr() as usize)
+        .filter(|&o| o <= text.len() && text.is_char_boundary(o))
+}
+
+/// Fallible `parse_text`: the first failing section is reported with its position in `text`.
+pub fn try_parse_text<T>(
+    text: &str,
+    parser: fn(&str) -> Result<T, ParseError>,
+    sections: fn(&str) -> Vec<&str>,
+) -> Result<Vec<T>> {
+    parse_sections(text, parser, sections).map_err(ParseError::report)
+}
+
+/// Fallible `parse_text_with_preview`.
+pub fn try_parse_text_with_preview<T: Debug>(
+    text: &str,
+    parser: fn(&str) -> Result<T, ParseError>,
+    sections: fn(&str) -> Vec<&str>,
+) -> Result<Vec<T>> {
+    show_items("Puzzle input", &lines(text));
+    let records = try_parse_text(text, parser, sections)?;
+    show_debug_items("Parsed representation", &records);
+    Ok(records)
+}
+
+/// Fallible `parse_day_with`: errors also name the day.
+pub fn try_parse_day<T>(
+    day: u8,
+    parser: fn(&str) -> Result<T, ParseError>,
+    sections: fn(&str) -> Vec<&str>,
+) -> Result<Vec<T>> {
+    let text = read_or_fetch_input(day)?;
+    parse_sections(&text, parser, sections).map_err(|e| e.in_day(day).report())
+}
+
+fn parse_sections<T>(
+    text: &str,
+    parser: fn(&str) -> Result<T, ParseError>,
+    sections: fn(&str) -> Vec<&str>,
+) -> Result<Vec<T>, ParseError> {
+    sections(text.trim_end())
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| parser(item).map_err(|e| e.within(text).in_section(i)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::paragraphs;
+
+    fn rotation(line: &str) -> Result<i32, ParseError> {
+        let n: i32 = ParseError::parse_field(line, &line[1..], "a number")?;
+        match &line[..1] {
+            "L" => Ok(-n),
+            "R" => Ok(n),
+            d => Err(ParseError::at(line, d, "expected L or R")),
+        }
+    }
+
+    #[test]
+    fn errors_are_located_in_the_whole_input() {
+        assert_eq!(
+            try_parse_text("L5\nR10\n", rotation, lines).unwrap(),
+            [-5, 10]
+        );
+        let err = parse_sections("L5\nR10\nR4x\n", rotation, lines).unwrap_err();
+        assert_eq!((err.section, err.line, err.column), (Some(2), 3, 2));
+        assert_eq!(err.text, "4x");
+        assert_eq!(
+            err.to_string(),
+            "section 3, line 3, column 2: expected a number, found \"4x\""
+        );
+        assert_eq!(err.snippet(), "     3 | R4x\n       |  ^^");
+    }
+
+    #[test]
+    fn paragraph_errors_point_at_the_right_line() {
+        let err = parse_sections("L1\nR2\n\nL3\nX4", rotation_sum, paragraphs).unwrap_err();
+        assert_eq!((err.section, err.line, err.column), (Some(1), 5, 1));
+        let report = format!("{:?}", err.in_day(1).report());
+        assert!(report.contains("day 1, section 2, line 5, column 1"));
+    }
+
+    #[test]
+    fn try_ints_rejects_overflow() {
+        assert_eq!(crate::parsers::try_ints("a-3 b40"), Ok(vec![-3, 40]));
+        let err = crate::parsers::try_ints("1 99999999999999999999").unwrap_err();
+        assert_eq!((err.column, err.text.len()), (3, 20));
+    }
+
+    /// Errors from the per-line parser are still placed correctly in the whole input.
+    fn rotation_sum(paragraph: &str) -> Result<i32, ParseError> {
+        paragraph.lines().map(rotation).sum()
+    }
+}
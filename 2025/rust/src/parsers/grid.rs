@@ -0,0 +1,567 @@
+//! Grid utilities (inspired by Norvig's AdventUtils Grid).
+//!
+//! `Grid<T>` has two storage backends behind one API:
+//! - dense: a row-major `Vec<T>` covering exactly `size`, for the rectangular grids most days use;
+//! - sparse: a `HashMap<Point, T>`, for grids with holes (`skip` chars, ragged lines) or cells
+//!   outside `size` (unbounded/infinite grids).
+
+use std::collections::{HashMap, HashSet};
+
+/// A 2D point `(x, y)` with `x` increasing to the right and `y` increasing downwards.
+pub type Point = (i32, i32);
+
+/// 4-connected neighbor directions: right, down, left, up.
+pub const DIRECTIONS4: [Point; 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// 8-connected neighbor directions (includes diagonals).
+pub const DIRECTIONS8: [Point; 8] = [
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (0, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+    (1, -1),
+];
+
+/// Add a vector `d` to point `p`.
+pub fn add2(p: Point, d: Point) -> Point {
+    (p.0 + d.0, p.1 + d.1)
+}
+
+/// Neighbours of this point using the given directions (can be used outside of a Grid).
+pub fn neighbours(point: Point, directions: &[Point]) -> Vec<Point> {
+    directions.iter().map(|&d| add2(point, d)).collect()
+}
+
+/// Missing-value behavior for `Grid` lookups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Missing<T> {
+    /// Mimic Python `KeyError`: accessing a missing point is considered an error; methods that
+    /// fetch values will not synthesize a cell and will typically skip or return `None`.
+    Error,
+    /// Mimic Python `None` default: accessing a missing point returns no value.
+    NoneValue,
+    /// Use a concrete value for any missing cell (including off-grid).
+    Value(T),
+}
+
+/// Missing-value behavior for character grids.
+pub type MissingChar = Missing<char>;
+
+impl<T> Missing<T> {
+    pub(super) fn value(&self) -> Option<&T> {
+        match self {
+            Missing::Value(v) => Some(v),
+            Missing::Error | Missing::NoneValue => None,
+        }
+    }
+
+    /// Apply `f` to the default value, keeping the policy.
+    pub fn map<U>(&self, f: impl FnOnce(&T) -> U) -> Missing<U> {
+        match self {
+            Missing::Error => Missing::Error,
+            Missing::NoneValue => Missing::NoneValue,
+            Missing::Value(v) => Missing::Value(f(v)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Cells<T> {
+    /// Row-major, `width * height` long.
+    Dense(Vec<T>),
+    Sparse(HashMap<Point, T>),
+}
+
+/// A 2D grid of cells (characters by default).
+///
+/// Coordinates are zero-based. Size is `(width, height)`.
+#[derive(Debug, Clone)]
+pub struct Grid<T = char> {
+    pub size: (i32, i32),
+    cells: Cells<T>,
+    directions: Vec<Point>,
+    missing: Missing<T>,
+    /// Toroidal mode: points wrap around the edges of `size`.
+    wrap: bool,
+}
+
+impl Grid<char> {
+    /// Initialize from a string (lines). Supports `skip` set and missing/default behavior.
+    /// Width is the maximum line length; height is the number of lines (a single trailing newline
+    /// does not count as an extra line). Cells with characters in `skip` are omitted from the
+    /// mapping. Rectangular text without skipped characters is stored densely.
+    pub fn from_str_with(
+        text: &str,
+        directions: &[Point],
+        skip: &HashSet<char>,
+        missing: MissingChar,
+    ) -> Self {
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        let rows: Vec<Vec<char>> = text.split('\n').map(|l| l.chars().collect()).collect();
+        let height = rows.len() as i32;
+        let width = rows.iter().map(|r| r.len() as i32).max().unwrap_or(0);
+        let rectangular = rows.iter().all(|r| r.len() as i32 == width);
+        if rectangular && !rows.iter().flatten().any(|ch| skip.contains(ch)) {
+            return Grid::from_vec((width, height), rows.concat(), directions, missing);
+        }
+        let cells = rows.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, ch)| !skip.contains(ch))
+                .map(move |(x, &ch)| ((x as i32, y as i32), ch))
+        });
+        Grid::from_cells((width, height), cells, directions, missing)
+    }
+
+    /// Initialize from a string with 4-neighborhood, no skip, and `None`-like missing.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(text: &str) -> Self {
+        Self::from_str_with(text, &DIRECTIONS4, &HashSet::new(), MissingChar::NoneValue)
+    }
+
+    /// Parse a character grid and convert every cell with `f`, e.g. digits to `u8` or `#`/`.`
+    /// to `bool`. The result uses the same storage, directions and missing policy.
+    pub fn parse_with<U>(
+        text: &str,
+        directions: &[Point],
+        missing: Missing<U>,
+        f: impl Fn(char) -> U,
+    ) -> Grid<U> {
+        let mut grid =
+            Grid::from_str_with(text, directions, &HashSet::new(), MissingChar::NoneValue)
+                .map(|&ch| f(ch));
+        grid.missing = missing;
+        grid
+    }
+
+    /// The contents of the grid as a rectangular list of rows. You can define a window
+    /// with `xrange` and `yrange`; or they default to the whole grid. For missing == Error/None,
+    /// the default fill is space `' '`; otherwise it is the provided `Value`.
+    pub fn to_rows(
+        &self,
+        xrange: Option<std::ops::Range<i32>>,
+        yrange: Option<std::ops::Range<i32>>,
+    ) -> Vec<Vec<char>> {
+        self.to_rows_or(xrange, yrange, ' ')
+    }
+}
+
+impl<T> Grid<T> {
+    /// Initialize a sparse grid from an iterator of cells and an explicit size. Any out-of-range
+    /// cells are kept, but `in_range` uses the provided `size`.
+    pub fn from_cells<I>(
+        size: (i32, i32),
+        cells: I,
+        directions: &[Point],
+        missing: Missing<T>,
+    ) -> Self
+    where
+        I: IntoIterator<Item = (Point, T)>,
+    {
+        Grid {
+            size,
+            cells: Cells::Sparse(cells.into_iter().collect()),
+            directions: directions.to_vec(),
+            missing,
+            wrap: false,
+        }
+    }
+
+    /// Initialize a dense grid from row-major `cells`, which must hold `width * height` values.
+    pub fn from_vec(
+        size: (i32, i32),
+        cells: Vec<T>,
+        directions: &[Point],
+        missing: Missing<T>,
+    ) -> Self {
+        assert_eq!(
+            cells.len(),
+            (size.0.max(0) * size.1.max(0)) as usize,
+            "dense grid needs width * height cells"
+        );
+        Grid {
+            size,
+            cells: Cells::Dense(cells),
+            directions: directions.to_vec(),
+            missing,
+            wrap: false,
+        }
+    }
+
+    /// A dense grid of `size` with every cell set to `value`.
+    pub fn filled(size: (i32, i32), value: T, directions: &[Point], missing: Missing<T>) -> Self
+    where
+        T: Clone,
+    {
+        let n = (size.0.max(0) * size.1.max(0)) as usize;
+        Grid::from_vec(size, vec![value; n], directions, missing)
+    }
+
+    /// Is the grid stored as a dense row-major `Vec`?
+    pub fn is_dense(&self) -> bool {
+        matches!(self.cells, Cells::Dense(_))
+    }
+
+    /// The neighbour directions used by `neighbours`.
+    pub fn directions(&self) -> &[Point] {
+        &self.directions
+    }
+
+    /// The missing-value policy.
+    pub fn missing(&self) -> &Missing<T> {
+        &self.missing
+    }
+
+    /// Turn toroidal (wrap-around) mode on or off. When on, `get`, `set`, `neighbours`,
+    /// `follow_line` and the search/region helpers treat the grid as a torus of `size`, like
+    /// robots patrolling a 101x103 room.
+    pub fn wrapping(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn is_wrapping(&self) -> bool {
+        self.wrap
+    }
+
+    /// `p` wrapped onto the torus of `size`. An axis of size zero (an empty grid) is left as is.
+    pub fn wrap_point(&self, p: Point) -> Point {
+        let wrap = |v: i32, size: i32| if size > 0 { v.rem_euclid(size) } else { v };
+        (wrap(p.0, self.size.0), wrap(p.1, self.size.1))
+    }
+
+    /// The point one step from `p` in direction `d`, wrapping in toroidal mode.
+    pub fn step(&self, p: Point, d: Point) -> Point {
+        let q = add2(p, d);
+        if self.wrap { self.wrap_point(q) } else { q }
+    }
+
+    fn locate(&self, p: Point) -> Point {
+        if self.wrap { self.wrap_point(p) } else { p }
+    }
+
+    /// Convert every cell with `f`, keeping size, storage, directions and missing policy.
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        let cells = match &self.cells {
+            Cells::Dense(v) => Cells::Dense(v.iter().map(&f).collect()),
+            Cells::Sparse(m) => Cells::Sparse(m.iter().map(|(&p, v)| (p, f(v))).collect()),
+        };
+        Grid {
+            size: self.size,
+            cells,
+            directions: self.directions.clone(),
+            missing: self.missing.map(&f),
+            wrap: self.wrap,
+        }
+    }
+
+    /// Switch to sparse storage (e.g. before setting cells outside `size`).
+    pub fn into_sparse(self) -> Self {
+        let cells = match self.cells {
+            Cells::Sparse(m) => m,
+            Cells::Dense(v) => dense_to_map(v, self.size.0),
+        };
+        Grid {
+            cells: Cells::Sparse(cells),
+            ..self
+        }
+    }
+
+    /// Switch to dense storage; cells within `size` that are absent become `fill`, and cells
+    /// outside `size` are dropped.
+    pub fn into_dense(self, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        match self.cells {
+            Cells::Dense(_) => self,
+            Cells::Sparse(mut m) => {
+                let (w, h) = self.size;
+                let cells = (0..h)
+                    .flat_map(|y| (0..w).map(move |x| (x, y)))
+                    .map(|p| m.remove(&p).unwrap_or_else(|| fill.clone()))
+                    .collect();
+                Grid {
+                    cells: Cells::Dense(cells),
+                    ..self
+                }
+            }
+        }
+    }
+
+    /// Return a copy of the grid with the same directions and missing behavior.
+    pub fn copy(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.clone()
+    }
+
+    /// Is the point within the range of the grid's size?
+    pub fn in_range(&self, p: Point) -> bool {
+        0 <= p.0 && p.0 < self.size.0 && 0 <= p.1 && p.1 < self.size.1
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        self.in_range(p).then(|| (p.1 * self.size.0 + p.0) as usize)
+    }
+
+    /// Is there a stored cell at `p` (ignoring the missing policy)?
+    pub fn contains(&self, p: Point) -> bool {
+        let p = self.locate(p);
+        match &self.cells {
+            Cells::Dense(_) => self.in_range(p),
+            Cells::Sparse(m) => m.contains_key(&p),
+        }
+    }
+
+    /// Follow a ray starting at `start`, stepping by `direction`, yielding points until out of range.
+    /// In toroidal mode the ray wraps and stops just before returning to `start`.
+    pub fn follow_line(&self, start: Point, direction: Point) -> Vec<Point> {
+        let mut pts = Vec::new();
+        if self.wrap {
+            let (w, h) = self.size;
+            if w <= 0 || h <= 0 {
+                return pts;
+            }
+            let start = self.wrap_point(start);
+            let mut cur = start;
+            for _ in 0..i64::from(w) * i64::from(h) {
+                pts.push(cur);
+                cur = self.step(cur, direction);
+                if cur == start {
+                    break;
+                }
+            }
+            return pts;
+        }
+        let mut cur = start;
+        while self.in_range(cur) {
+            pts.push(cur);
+            cur = add2(cur, direction);
+        }
+        pts
+    }
+
+    /// Points on the grid that neighbour `point` according to this grid's directions.
+    /// Includes points that are not present in `cells` when `missing` is `Value(_)`.
+    pub fn neighbours(&self, p: Point) -> Vec<Point> {
+        let synthesize_missing = matches!(self.missing, Missing::Value(_));
+        self.directions
+            .iter()
+            .map(|&d| self.step(p, d))
+            .filter(|&q| synthesize_missing || self.contains(q))
+            .collect()
+    }
+
+    /// The contents of the neighbouring points, using missing/default behavior.
+    pub fn neighbour_contents(&self, p: Point) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.neighbours(p)
+            .into_iter()
+            .filter_map(|q| self.get(q))
+            .collect()
+    }
+
+    /// A reference to the cell value at `p`, applying the grid's missing policy.
+    pub fn get_ref(&self, p: Point) -> Option<&T> {
+        let p = self.locate(p);
+        let stored = match &self.cells {
+            Cells::Dense(v) => self.index(p).map(|i| &v[i]),
+            Cells::Sparse(m) => m.get(&p),
+        };
+        stored.or_else(|| self.missing.value())
+    }
+
+    /// Get the cell value at `p`, applying the grid's missing policy.
+    pub fn get(&self, p: Point) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.get_ref(p).cloned()
+    }
+
+    /// Set the cell value at `p`. Setting a point outside `size` on a dense grid switches it to
+    /// sparse storage so that unbounded grids keep working.
+    pub fn set(&mut self, p: Point, value: T) {
+        let p = self.locate(p);
+        let index = self.index(p);
+        if let Cells::Dense(v) = &mut self.cells {
+            if let Some(i) = index {
+                v[i] = value;
+                return;
+            }
+            self.cells = Cells::Sparse(dense_to_map(std::mem::take(v), self.size.0));
+        }
+        if let Cells::Sparse(m) = &mut self.cells {
+            m.insert(p, value);
+        }
+    }
+
+    /// Remove the cell at `p` (sparse storage only; dense grids have no holes).
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        let p = self.locate(p);
+        match &mut self.cells {
+            Cells::Dense(_) => None,
+            Cells::Sparse(m) => m.remove(&p),
+        }
+    }
+
+    /// All stored cells as `(point, value)`. Dense grids iterate in row-major order.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (Point, &T)> + '_> {
+        match &self.cells {
+            Cells::Dense(v) => {
+                let w = self.size.0;
+                Box::new(
+                    v.iter()
+                        .enumerate()
+                        .map(move |(i, t)| (dense_point(i, w), t)),
+                )
+            }
+            Cells::Sparse(m) => Box::new(m.iter().map(|(&p, t)| (p, t))),
+        }
+    }
+
+    /// All points whose value satisfies `pred`.
+    pub fn find_where(&self, pred: impl Fn(&T) -> bool) -> Vec<Point> {
+        self.iter()
+            .filter(|(_, t)| pred(t))
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    /// All points that contain one of the given values.
+    pub fn find_all<C: IntoIterator<Item = T>>(&self, contents: C) -> Vec<Point>
+    where
+        T: PartialEq,
+    {
+        let wanted: Vec<T> = contents.into_iter().collect();
+        self.find_where(|t| wanted.contains(t))
+    }
+
+    /// The contents of the grid as a rectangular list of rows over the given window (default:
+    /// the whole grid). Cells with no value use the missing `Value`, or else `fill`.
+    pub fn to_rows_or(
+        &self,
+        xrange: Option<std::ops::Range<i32>>,
+        yrange: Option<std::ops::Range<i32>>,
+        fill: T,
+    ) -> Vec<Vec<T>>
+    where
+        T: Clone,
+    {
+        let xr = xrange.unwrap_or(0..self.size.0);
+        let yr = yrange.unwrap_or(0..self.size.1);
+        yr.map(|y| {
+            xr.clone()
+                .map(|x| self.get((x, y)).unwrap_or_else(|| fill.clone()))
+                .collect()
+        })
+        .collect()
+    }
+}
+
+/// The point of the `i`th cell of a dense grid `width` wide. A zero-width grid (e.g. parsed from
+/// `""`) stores no cells, but is guarded anyway rather than dividing by zero.
+fn dense_point(i: usize, width: i32) -> Point {
+    let width = width.max(1);
+    ((i as i32) % width, (i as i32) / width)
+}
+
+fn dense_to_map<T>(cells: Vec<T>, width: i32) -> HashMap<Point, T> {
+    cells
+        .into_iter()
+        .enumerate()
+        .map(|(i, t)| (dense_point(i, width), t))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "#.#\n.@.\n#.#\n";
+
+    #[test]
+    fn rectangular_text_is_dense_and_ragged_is_sparse() {
+        let grid = Grid::from_str(TEXT);
+        assert!(grid.is_dense());
+        assert_eq!(grid.size, (3, 3));
+        let ragged = Grid::from_str("ab\nc");
+        assert!(!ragged.is_dense());
+        assert_eq!(ragged.get((1, 1)), None);
+    }
+
+    #[test]
+    fn backends_agree() {
+        let dense = Grid::from_str_with(TEXT, &DIRECTIONS8, &HashSet::new(), MissingChar::Error);
+        let sparse = dense.clone().into_sparse();
+        for p in [(0, 0), (1, 1), (2, 2), (-1, 0), (3, 3)] {
+            assert_eq!(dense.get(p), sparse.get(p));
+            let (mut a, mut b) = (dense.neighbours(p), sparse.neighbours(p));
+            a.sort_unstable();
+            b.sort_unstable();
+            assert_eq!(a, b);
+        }
+        let mut a = dense.find_all(['#']);
+        let mut b = sparse.find_all(['#']);
+        a.sort_unstable();
+        b.sort_unstable();
+        assert_eq!(a, b);
+        assert_eq!(dense.to_rows(None, None), sparse.to_rows(None, None));
+        assert_eq!(
+            dense.follow_line((0, 0), (1, 1)),
+            sparse.follow_line((0, 0), (1, 1))
+        );
+    }
+
+    #[test]
+    fn missing_value_synthesizes_neighbours() {
+        let grid =
+            Grid::from_str_with(TEXT, &DIRECTIONS4, &HashSet::new(), MissingChar::Value('.'));
+        assert_eq!(grid.neighbours((0, 0)).len(), 4);
+        assert_eq!(grid.get((-5, -5)), Some('.'));
+    }
+
+    #[test]
+    fn parse_with_maps_cells() {
+        let digits = Grid::parse_with("123\n456", &DIRECTIONS4, Missing::Error, |c| {
+            c.to_digit(10).unwrap() as u8
+        });
+        assert_eq!(digits.get((2, 1)), Some(6));
+        let walls = Grid::parse_with(TEXT, &DIRECTIONS4, Missing::Value(true), |c| c == '#');
+        assert_eq!(walls.find_all([true]).len(), 4);
+        assert_eq!(walls.get((9, 9)), Some(true));
+    }
+
+    #[test]
+    fn setting_outside_a_dense_grid_goes_sparse() {
+        let mut grid = Grid::from_str(TEXT);
+        grid.set((1, 1), 'x');
+        assert!(grid.is_dense());
+        grid.set((10, 10), 'y');
+        assert!(!grid.is_dense());
+        assert_eq!(grid.get((1, 1)), Some('x'));
+        assert_eq!(grid.get((10, 10)), Some('y'));
+        assert!(grid.into_dense(' ').is_dense());
+    }
+
+    #[test]
+    fn empty_grids() {
+        let mut grid = Grid::from_str("");
+        assert_eq!(grid.iter().count(), 0);
+        grid.set((2, 0), 'x');
+        assert_eq!(grid.get((2, 0)), Some('x'));
+        assert_eq!(Grid::from_str("").into_sparse().iter().count(), 0);
+        let mut torus = Grid::from_str("").wrapping(true);
+        assert_eq!(torus.wrap_point((-3, 4)), (-3, 0));
+        torus.set((5, 0), 'y');
+        assert_eq!(torus.get((5, 0)), Some('y'));
+        assert!(torus.follow_line((0, 0), (1, 0)).is_empty());
+    }
+}
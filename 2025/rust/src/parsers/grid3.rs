@@ -0,0 +1,277 @@
+//! A sparse 3D grid of voxels, with the same missing-value policy as `Grid`.
+//!
+//! Typical uses are lava droplets given as `x,y,z` lines (2022 day 18) and 3D cellular automata
+//! seeded from a 2D slice (2020 day 17).
+
+use super::error::ParseError;
+use super::grid::{Missing, MissingChar};
+use super::point::Point3;
+use super::stream::IntScanner;
+use std::collections::{HashMap, HashSet};
+
+pub type Voxel = Point3<i32>;
+
+/// Face-adjacent neighbour offsets.
+pub const DIRECTIONS6: [Voxel; 6] = [
+    Point3::new(1, 0, 0),
+    Point3::new(-1, 0, 0),
+    Point3::new(0, 1, 0),
+    Point3::new(0, -1, 0),
+    Point3::new(0, 0, 1),
+    Point3::new(0, 0, -1),
+];
+
+/// Face, edge and corner neighbour offsets.
+pub const DIRECTIONS26: [Voxel; 26] = {
+    let mut out = [Point3::new(0, 0, 0); 26];
+    let (mut i, mut n) = (0, 0);
+    while n < 27 {
+        if n != 13 {
+            out[i] = Point3::new(n % 3 - 1, n / 3 % 3 - 1, n / 9 - 1);
+            i += 1;
+        }
+        n += 1;
+    }
+    out
+};
+
+/// A sparse 3D grid (characters by default). Only set voxels are stored; lookups elsewhere follow
+/// the `missing` policy.
+#[derive(Debug, Clone)]
+pub struct Grid3<T = char> {
+    cells: HashMap<Voxel, T>,
+    directions: &'static [Voxel],
+    missing: Missing<T>,
+}
+
+impl<T> Grid3<T> {
+    pub fn new(directions: &'static [Voxel], missing: Missing<T>) -> Self {
+        Grid3 {
+            cells: HashMap::new(),
+            directions,
+            missing,
+        }
+    }
+
+    /// `value` at each of `points`.
+    pub fn from_points(
+        points: impl IntoIterator<Item = Voxel>,
+        value: T,
+        directions: &'static [Voxel],
+        missing: Missing<T>,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = Grid3::new(directions, missing);
+        grid.cells
+            .extend(points.into_iter().map(|p| (p, value.clone())));
+        grid
+    }
+
+    pub fn directions(&self) -> &[Voxel] {
+        self.directions
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether a value is stored at `p` (ignoring the missing policy).
+    pub fn contains(&self, p: Voxel) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    /// A reference to the value at `p`, applying the missing policy.
+    pub fn get_ref(&self, p: Voxel) -> Option<&T> {
+        self.cells.get(&p).or_else(|| self.missing.value())
+    }
+
+    pub fn get(&self, p: Voxel) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.get_ref(p).cloned()
+    }
+
+    pub fn set(&mut self, p: Voxel, value: T) {
+        self.cells.insert(p, value);
+    }
+
+    pub fn remove(&mut self, p: Voxel) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Voxel, &T)> {
+        self.cells.iter().map(|(&p, t)| (p, t))
+    }
+
+    /// Neighbouring points by this grid's directions, whether set or not (the grid is unbounded).
+    pub fn neighbours(&self, p: Voxel) -> Vec<Voxel> {
+        self.directions.iter().map(|&d| p + d).collect()
+    }
+
+    /// The contents of the neighbouring points, using the missing policy.
+    pub fn neighbour_contents(&self, p: Voxel) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.neighbours(p)
+            .into_iter()
+            .filter_map(|q| self.get(q))
+            .collect()
+    }
+
+    /// Inclusive `(min, max)` corners of the stored voxels.
+    pub fn bounds(&self) -> Option<(Voxel, Voxel)> {
+        let mut points = self.cells.keys();
+        let &first = points.next()?;
+        Some(points.fold((first, first), |(lo, hi), p| {
+            (
+                Point3::new(lo.x.min(p.x), lo.y.min(p.y), lo.z.min(p.z)),
+                Point3::new(hi.x.max(p.x), hi.y.max(p.y), hi.z.max(p.z)),
+            )
+        }))
+    }
+
+    /// Faces of voxels satisfying `solid` that touch a voxel that does not.
+    pub fn surface_area(&self, solid: impl Fn(&T) -> bool) -> usize {
+        let is_solid = |p: Voxel| self.cells.get(&p).is_some_and(&solid);
+        self.cells
+            .iter()
+            .filter(|&(_, t)| solid(t))
+            .map(|(&p, _)| DIRECTIONS6.iter().filter(|&&d| !is_solid(p + d)).count())
+            .sum()
+    }
+
+    /// Non-solid voxels reachable from outside the bounding box (padded by one) via face moves.
+    /// Anything non-solid inside the box but not in this set is an enclosed pocket.
+    pub fn exterior(&self, solid: impl Fn(&T) -> bool) -> HashSet<Voxel> {
+        let mut outside = HashSet::new();
+        let Some((lo, hi)) = self.bounds() else {
+            return outside;
+        };
+        let one = Point3::new(1, 1, 1);
+        let (lo, hi) = (lo - one, hi + one);
+        let in_box = |p: Voxel| {
+            (lo.x..=hi.x).contains(&p.x)
+                && (lo.y..=hi.y).contains(&p.y)
+                && (lo.z..=hi.z).contains(&p.z)
+        };
+        let is_solid = |p: Voxel| self.cells.get(&p).is_some_and(&solid);
+        let mut stack = vec![lo];
+        outside.insert(lo);
+        while let Some(p) = stack.pop() {
+            for &d in &DIRECTIONS6 {
+                let q = p + d;
+                if in_box(q) && !is_solid(q) && outside.insert(q) {
+                    stack.push(q);
+                }
+            }
+        }
+        outside
+    }
+
+    /// Faces of solid voxels that touch the exterior (excludes faces facing enclosed pockets).
+    pub fn exterior_surface_area(&self, solid: impl Fn(&T) -> bool) -> usize {
+        let outside = self.exterior(&solid);
+        self.cells
+            .iter()
+            .filter(|&(_, t)| solid(t))
+            .map(|(&p, _)| {
+                DIRECTIONS6
+                    .iter()
+                    .filter(|&&d| outside.contains(&(p + d)))
+                    .count()
+            })
+            .sum()
+    }
+}
+
+impl Grid3<char> {
+    /// Lines of `x,y,z` integers, each marked with `value`. A line without exactly three integers,
+    /// or with one that does not fit in an `i32`, is a `ParseError`.
+    pub fn from_coords(
+        text: &str,
+        value: char,
+        directions: &'static [Voxel],
+        missing: MissingChar,
+    ) -> Result<Self, ParseError> {
+        let points = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let coords = IntScanner::signed(line)
+                    .map(|lexeme| ParseError::parse_field(text, lexeme, "a 32-bit coordinate"))
+                    .collect::<Result<Vec<i32>, _>>()?;
+                match coords[..] {
+                    [x, y, z] => Ok(Point3::new(x, y, z)),
+                    _ => Err(ParseError::at(text, line, "expected x,y,z")),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Grid3::from_points(points, value, directions, missing))
+    }
+
+    /// A 2D character picture placed in the plane `z`, leaving out `skip` characters.
+    pub fn from_slice(
+        text: &str,
+        z: i32,
+        skip: &[char],
+        directions: &'static [Voxel],
+        missing: MissingChar,
+    ) -> Self {
+        let mut grid = Grid3::new(directions, missing);
+        for (y, line) in text.lines().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                if !skip.contains(&ch) {
+                    grid.set(Point3::new(x as i32, y as i32, z), ch);
+                }
+            }
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2022 day 18's larger example droplet.
+    const DROPLET: &str =
+        "2,2,2\n1,2,2\n3,2,2\n2,1,2\n2,3,2\n2,2,1\n2,2,3\n2,2,4\n2,2,6\n1,2,5\n3,2,5\n2,1,5\n2,3,5";
+
+    #[test]
+    fn droplet_surface() {
+        let grid = Grid3::from_coords(DROPLET, '#', &DIRECTIONS6, Missing::Value('.')).unwrap();
+        assert_eq!(grid.len(), 13);
+        assert_eq!(grid.surface_area(|&c| c == '#'), 64);
+        assert_eq!(grid.exterior_surface_area(|&c| c == '#'), 58);
+        assert_eq!(grid.get(Point3::new(2, 2, 5)), Some('.'));
+        assert!(Grid3::from_coords("1,2", '#', &DIRECTIONS6, Missing::Error).is_err());
+        let err = Grid3::from_coords("1,2,3\n4,5,3000000000", '#', &DIRECTIONS6, Missing::Error)
+            .unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.text.as_str()),
+            (2, 5, "3000000000")
+        );
+    }
+
+    #[test]
+    fn slices_and_neighbourhoods() {
+        let grid = Grid3::from_slice(".#.\n..#\n###", 0, &['.'], &DIRECTIONS26, Missing::Error);
+        assert_eq!(grid.len(), 5);
+        assert_eq!(DIRECTIONS26.len(), 26);
+        assert!(!DIRECTIONS26.contains(&Point3::new(0, 0, 0)));
+        assert_eq!(grid.neighbour_contents(Point3::new(1, 1, 0)).len(), 5);
+        assert_eq!(grid.neighbour_contents(Point3::new(1, 1, 1)).len(), 5);
+        assert_eq!(
+            grid.bounds(),
+            Some((Point3::new(0, 0, 0), Point3::new(2, 2, 0)))
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Hexagonal grid coordinates.
+//!
+//! Hexes use axial coordinates `(q, r)`; the implied cube coordinate is `s = -q - r`. The six
+//! neighbour offsets are the same whichever way the hexes are drawn; only the names of the
+//! directions differ:
+//! - pointy-topped hexes sit in rows and step `e`, `w`, `ne`, `nw`, `se`, `sw` (2020 day 24);
+//! - flat-topped hexes sit in columns and step `n`, `s`, `ne`, `nw`, `se`, `sw` (2017 day 11).
+
+use color_eyre::eyre::{Result, bail};
+use std::collections::HashMap;
+use std::ops::{Add, AddAssign, Sub};
+
+/// A hex in axial coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// Axial offsets of the six neighbours.
+pub const HEX_DIRECTIONS: [Hex; 6] = [
+    Hex::new(1, 0),
+    Hex::new(1, -1),
+    Hex::new(0, -1),
+    Hex::new(-1, 0),
+    Hex::new(-1, 1),
+    Hex::new(0, 1),
+];
+
+/// Which way up the hexes are drawn, which decides how direction names map to offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexLayout {
+    /// Flat sides top and bottom: `n`/`s` exist, `e`/`w` do not.
+    Flat,
+    /// Points at top and bottom: `e`/`w` exist, `n`/`s` do not.
+    Pointy,
+}
+
+impl Hex {
+    pub const fn new(q: i32, r: i32) -> Self {
+        Hex { q, r }
+    }
+
+    /// From cube coordinates; `q + r + s` must be zero.
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Self {
+        debug_assert_eq!(q + r + s, 0, "cube coordinates must sum to zero");
+        Hex { q, r }
+    }
+
+    /// The third cube coordinate.
+    pub fn s(self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn cube(self) -> (i32, i32, i32) {
+        (self.q, self.r, self.s())
+    }
+
+    /// Number of steps between two hexes.
+    pub fn distance(self, other: Hex) -> i32 {
+        let d = self - other;
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+
+    pub fn neighbours(self) -> [Hex; 6] {
+        HEX_DIRECTIONS.map(|d| self + d)
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+    fn add(self, rhs: Hex) -> Hex {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+    fn sub(self, rhs: Hex) -> Hex {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, rhs: Hex) {
+        *self = *self + rhs;
+    }
+}
+
+impl HexLayout {
+    /// The offset for a direction name such as `ne` or `s`.
+    pub fn direction(self, name: &str) -> Result<Hex> {
+        let d = match (self, name) {
+            (_, "ne") => Hex::new(1, -1),
+            (_, "sw") => Hex::new(-1, 1),
+            (HexLayout::Pointy, "e") => Hex::new(1, 0),
+            (HexLayout::Pointy, "w") => Hex::new(-1, 0),
+            (HexLayout::Pointy, "nw") => Hex::new(0, -1),
+            (HexLayout::Pointy, "se") => Hex::new(0, 1),
+            (HexLayout::Flat, "n") => Hex::new(0, -1),
+            (HexLayout::Flat, "s") => Hex::new(0, 1),
+            (HexLayout::Flat, "nw") => Hex::new(-1, 0),
+            (HexLayout::Flat, "se") => Hex::new(1, 0),
+            _ => bail!("{name:?} is not a direction on a {self:?} hex grid"),
+        };
+        Ok(d)
+    }
+
+    /// Parse a run of direction names, either separated by commas/whitespace (`ne,ne,s`) or run
+    /// together (`esenee`). Two-letter names are preferred when both readings are possible.
+    pub fn parse_steps(self, text: &str) -> Result<Vec<Hex>> {
+        let mut steps = Vec::new();
+        for token in text.split(|c: char| c == ',' || c.is_whitespace()) {
+            let mut rest = token;
+            while !rest.is_empty() {
+                let two = rest.get(..2).and_then(|s| self.direction(s).ok());
+                let (d, len) = match two {
+                    Some(d) => (d, 2),
+                    None => (self.direction(rest.get(..1).unwrap_or(rest))?, 1),
+                };
+                steps.push(d);
+                rest = &rest[len..];
+            }
+        }
+        Ok(steps)
+    }
+
+    /// The hex reached by following `text` from the origin.
+    pub fn walk(self, text: &str) -> Result<Hex> {
+        Ok(self
+            .parse_steps(text)?
+            .into_iter()
+            .fold(Hex::default(), Add::add))
+    }
+}
+
+/// A sparse hex grid; only set hexes are stored (e.g. the flipped floor tiles of 2020 day 24).
+#[derive(Debug, Clone, Default)]
+pub struct HexGrid<T> {
+    pub cells: HashMap<Hex, T>,
+}
+
+impl<T> HexGrid<T> {
+    pub fn new() -> Self {
+        HexGrid {
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, h: Hex) -> Option<&T> {
+        self.cells.get(&h)
+    }
+
+    pub fn set(&mut self, h: Hex, value: T) {
+        self.cells.insert(h, value);
+    }
+
+    /// Values of the neighbours of `h` that are set.
+    pub fn neighbour_contents(&self, h: Hex) -> Vec<&T> {
+        h.neighbours()
+            .iter()
+            .filter_map(|n| self.cells.get(n))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_distances() {
+        // 2017 day 11 examples.
+        let distance = |s| HexLayout::Flat.walk(s).unwrap().distance(Hex::default());
+        assert_eq!(distance("ne,ne,ne"), 3);
+        assert_eq!(distance("ne,ne,sw,sw"), 0);
+        assert_eq!(distance("ne,ne,s,s"), 2);
+        assert_eq!(distance("se,sw,se,sw,sw"), 3);
+        assert!(HexLayout::Flat.walk("e").is_err());
+    }
+
+    #[test]
+    fn pointy_run_together() {
+        // 2020 day 24: `nwwswee` comes back to the start, `esew` ends south-east.
+        assert_eq!(HexLayout::Pointy.walk("nwwswee").unwrap(), Hex::default());
+        assert_eq!(HexLayout::Pointy.walk("esew").unwrap(), Hex::new(0, 1));
+        assert_eq!(HexLayout::Pointy.parse_steps("sesenw").unwrap().len(), 3);
+        assert_eq!(Hex::new(2, -1).cube(), (2, -1, -1));
+        assert_eq!(Hex::from_cube(0, 0, 0).neighbours().len(), 6);
+    }
+}
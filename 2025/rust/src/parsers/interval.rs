@@ -0,0 +1,328 @@
+//! Sets of integers stored as disjoint inclusive ranges.
+//!
+//! `IntervalSet` replaces hand-merged `(lo, hi)` lists and `HashSet`s of every member:
+//!
+//! ```ignore
+//! let fresh: IntervalSet<u64> = "3-5,10-14,16-20,12-18".parse()?;
+//! assert!(fresh.contains(17));
+//! assert_eq!(fresh.len(), 14);
+//! ```
+//!
+//! Ranges are kept in a `BTreeMap` from start to end, merged with any they overlap or touch, so
+//! membership is O(log n). `split_by` and `map_by` cut the set along a mapping table such as
+//! 2023 day 5's seed-to-soil maps.
+
+use super::error::ParseError;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Integer types usable as interval bounds.
+pub trait Discrete: Copy + Ord + FromStr {
+    fn succ(self) -> Option<Self>;
+    fn pred(self) -> Option<Self>;
+    /// How many values lie in `lo..=hi` (`lo <= hi`).
+    fn count(lo: Self, hi: Self) -> u128;
+    /// `self - from + to`: where `self` lands when `from` is moved to `to`.
+    fn shift(self, from: Self, to: Self) -> Self;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {$(
+        impl Discrete for $t {
+            fn succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn count(lo: Self, hi: Self) -> u128 {
+                (hi as i128 - lo as i128 + 1) as u128
+            }
+
+            fn shift(self, from: Self, to: Self) -> Self {
+                (self as i128 - from as i128 + to as i128) as $t
+            }
+        }
+    )*};
+}
+
+impl_discrete!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A set of integers, stored as disjoint, non-adjacent inclusive ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet<T> {
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// Does the range from `end` run into a range starting at `start`?
+    fn touches(end: T, start: T) -> bool {
+        end.succ().is_none_or(|after| start <= after)
+    }
+
+    /// Add every value in `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut lo, mut hi) = range.into_inner();
+        if lo > hi {
+            return;
+        }
+        if let Some((&start, &end)) = self.ranges.range(..=lo).next_back()
+            && Self::touches(end, lo)
+        {
+            lo = start;
+            hi = hi.max(end);
+        }
+        while let Some((&start, &end)) = self.ranges.range(lo..).next()
+            && Self::touches(hi, start)
+        {
+            self.ranges.remove(&start);
+            hi = hi.max(end);
+        }
+        self.ranges.insert(lo, hi);
+    }
+
+    /// Take every value in `range` out of the set, splitting ranges that straddle its ends.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (lo, hi) = range.into_inner();
+        if lo > hi {
+            return;
+        }
+        let first = match self.ranges.range(..lo).next_back() {
+            Some((&start, &end)) if end >= lo => start,
+            _ => lo,
+        };
+        let overlapping: Vec<(T, T)> = self
+            .ranges
+            .range(first..=hi)
+            .map(|(&start, &end)| (start, end))
+            .collect();
+        for (start, end) in overlapping {
+            self.ranges.remove(&start);
+            if start < lo {
+                self.ranges.insert(start, lo.pred().expect("start < lo"));
+            }
+            if end > hi {
+                self.ranges.insert(hi.succ().expect("end > hi"), end);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value <= end)
+    }
+
+    /// Is all of `range` in the set?
+    pub fn contains_range(&self, range: RangeInclusive<T>) -> bool {
+        let (lo, hi) = range.into_inner();
+        lo > hi
+            || self
+                .ranges
+                .range(..=lo)
+                .next_back()
+                .is_some_and(|(_, &end)| hi <= end)
+    }
+
+    /// The number of values in the set.
+    pub fn len(&self) -> u128 {
+        self.iter().map(|r| T::count(*r.start(), *r.end())).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of disjoint ranges.
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// The disjoint ranges in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..=end)
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.keys().next().copied()
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.values().next_back().copied()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut out = self.clone();
+        for range in other.iter() {
+            out.insert(range);
+        }
+        out
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        let mut out = IntervalSet::new();
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let lo = *x.start().max(y.start());
+            let hi = *x.end().min(y.end());
+            if lo <= hi {
+                out.ranges.insert(lo, hi);
+            }
+            if x.end() < y.end() {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        out
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut out = self.clone();
+        for range in other.iter() {
+            out.remove(range);
+        }
+        out
+    }
+
+    /// Cut the set along `table`'s ranges: each piece is paired with the index of the table
+    /// range containing it, or `None` for values no table range covers. Table ranges should not
+    /// overlap.
+    pub fn split_by(&self, table: &[RangeInclusive<T>]) -> Vec<(RangeInclusive<T>, Option<usize>)> {
+        let mut pieces = Vec::new();
+        let mut uncovered = self.clone();
+        for (i, range) in table.iter().enumerate() {
+            let mut part = IntervalSet::new();
+            part.insert(range.clone());
+            for piece in self.intersection(&part).iter() {
+                uncovered.remove(piece.clone());
+                pieces.push((piece, Some(i)));
+            }
+        }
+        pieces.extend(uncovered.iter().map(|piece| (piece, None)));
+        pieces.sort_by_key(|(piece, _)| *piece.start());
+        pieces
+    }
+
+    /// Send each value through a mapping table of `(source range, destination start)` entries;
+    /// values outside every source range map to themselves.
+    pub fn map_by(&self, table: &[(RangeInclusive<T>, T)]) -> Self {
+        let sources: Vec<RangeInclusive<T>> = table.iter().map(|(src, _)| src.clone()).collect();
+        self.split_by(&sources)
+            .into_iter()
+            .map(|(piece, entry)| match entry {
+                None => piece,
+                Some(i) => {
+                    let (from, to) = (*table[i].0.start(), table[i].1);
+                    piece.start().shift(from, to)..=piece.end().shift(from, to)
+                }
+            })
+            .collect()
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/// Parse an inclusive range written `a-b`, e.g. `3-5` or `-5--3`.
+pub fn parse_interval<T: Discrete>(text: &str) -> Result<RangeInclusive<T>, ParseError> {
+    let dash = text
+        .get(1..)
+        .and_then(|rest| rest.find('-'))
+        .map(|i| i + 1)
+        .ok_or_else(|| ParseError::at(text, text, "expected a range like 3-5"))?;
+    let (lo, hi) = (&text[..dash], &text[dash + 1..]);
+    let lo: T = ParseError::parse_field(text, lo, "a range start")?;
+    let hi: T = ParseError::parse_field(text, hi, "a range end")?;
+    if lo > hi {
+        return Err(ParseError::at(text, text, "range ends before it starts"));
+    }
+    Ok(lo..=hi)
+}
+
+impl<T: Discrete> FromStr for IntervalSet<T> {
+    type Err = ParseError;
+
+    /// Ranges like `3-5` separated by commas or whitespace.
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        text.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| parse_interval(part).map_err(|e| e.within(text)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(text: &str) -> IntervalSet<i64> {
+        text.parse().unwrap()
+    }
+
+    fn ranges(set: &IntervalSet<i64>) -> Vec<(i64, i64)> {
+        set.iter().map(|r| (*r.start(), *r.end())).collect()
+    }
+
+    #[test]
+    fn insert_merges_and_remove_splits() {
+        let mut fresh = set("3-5 10-14\n16-20,12-18");
+        assert_eq!(ranges(&fresh), [(3, 5), (10, 20)]);
+        assert_eq!(fresh.len(), 14);
+        fresh.insert(6..=9);
+        assert_eq!(ranges(&fresh), [(3, 20)]);
+        fresh.remove(8..=8);
+        fresh.remove(18..=30);
+        assert_eq!(ranges(&fresh), [(3, 7), (9, 17)]);
+        assert!(fresh.contains(3) && fresh.contains(17) && !fresh.contains(8));
+        assert!(fresh.contains_range(10..=15) && !fresh.contains_range(6..=9));
+
+        let mut edges: IntervalSet<u8> = [250..=255, 0..=1].into_iter().collect();
+        edges.insert(2..=249);
+        assert_eq!(edges.len(), 256);
+        edges.remove(0..=255);
+        assert!(edges.is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let (a, b) = (set("-5--3,0-9"), set("-4-2 8-12"));
+        assert_eq!(ranges(&a.union(&b)), [(-5, 12)]);
+        assert_eq!(ranges(&a.intersection(&b)), [(-4, -3), (0, 2), (8, 9)]);
+        assert_eq!(ranges(&a.difference(&b)), [(-5, -5), (3, 7)]);
+        let err = "3-5,7-x".parse::<IntervalSet<u64>>().unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (7, "expected a range end")
+        );
+        assert!(parse_interval::<u64>("5-3").is_err());
+    }
+
+    #[test]
+    fn mapping_tables() {
+        // 2023 day 5: seed ranges through the seed-to-soil map (98..=99 -> 50, 50..=97 -> 52).
+        let seeds = set("79-92 55-67");
+        let table = [(98..=99, 50), (50..=97, 52)];
+        assert_eq!(ranges(&seeds.map_by(&table)), [(57, 69), (81, 94)]);
+        let pieces = set("40-99").split_by(&[98..=99, 50..=97]);
+        let summary: Vec<_> = pieces.iter().map(|(r, i)| (*r.start(), *i)).collect();
+        assert_eq!(summary, [(40, None), (50, Some(1)), (98, Some(0))]);
+    }
+}
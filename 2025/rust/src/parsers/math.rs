@@ -0,0 +1,386 @@
+//! Number theory and combinatorics that keep coming back: gcd/lcm, modular arithmetic, the
+//! Chinese remainder theorem, exact linear systems, digit tricks and binomials.
+//!
+//! Everything is overflow-checked: functions that can overflow return `Option` rather than
+//! wrapping, and modular products go through `i128`.
+//!
+//! ```ignore
+//! // 2022 day 11 part 2: worry levels only matter modulo the monkeys' divisors.
+//! let bound = lcm_all(&divisors).expect("divisors overflow");
+//! // 2024 day 13: how many presses of A and B reach the prize?
+//! let presses = solve_integer(&[vec![ax, bx], vec![ay, by]], &[px, py]);
+//! ```
+
+use std::fmt;
+
+/// Greatest common divisor, always non-negative; `gcd(0, 0) == Some(0)`. `None` if the result
+/// is 2⁶³, which only happens for `i64::MIN` with `0` or itself.
+pub fn gcd(a: i64, b: i64) -> Option<i64> {
+    i64::try_from(gcd_u128(a.unsigned_abs().into(), b.unsigned_abs().into())).ok()
+}
+
+/// Greatest common divisor of all `values` (0 for none); `None` if it is 2⁶³.
+pub fn gcd_all(values: &[i64]) -> Option<i64> {
+    values.iter().try_fold(0, |g, &v| gcd(g, v))
+}
+
+/// Least common multiple, non-negative; `None` on overflow.
+pub fn lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    // In `u128`, so neither `gcd(i64::MIN, i64::MIN)` nor the absolute value can overflow.
+    let (a, b) = (u128::from(a.unsigned_abs()), u128::from(b.unsigned_abs()));
+    i64::try_from(a / gcd_u128(a, b) * b).ok()
+}
+
+/// Least common multiple of all `values` (1 for none); `None` on overflow.
+pub fn lcm_all(values: &[i64]) -> Option<i64> {
+    values.iter().try_fold(1, |l, &v| lcm(l, v))
+}
+
+/// `(g, x, y)` with `a*x + b*y == g == gcd(a, b)`; `None` if `g` is 2⁶³ (see `gcd`).
+pub fn extended_gcd(a: i64, b: i64) -> Option<(i64, i64, i64)> {
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+    Some((
+        i64::try_from(old_r).ok()?,
+        i64::try_from(old_x).ok()?,
+        i64::try_from(old_y).ok()?,
+    ))
+}
+
+/// `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime. `m` must be positive.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus must be positive");
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m)?;
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base^exp mod m`, in `0..m`. `m` must be positive.
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+    assert!(m > 0, "modulus must be positive");
+    let m = m as i128;
+    let mut base = (base as i128).rem_euclid(m);
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as i64
+}
+
+/// Solve `x ≡ residue (mod modulus)` for every pair, returning `(x, lcm of the moduli)` with
+/// `x` in `0..lcm`. Moduli need not be coprime; `None` if the congruences conflict or the lcm
+/// overflows.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0i64, 1i64), |(x, m), &(r, n)| {
+            assert!(n > 0, "modulus must be positive");
+            let (g, p, _) = extended_gcd(m, n)?;
+            let diff = r as i128 - x as i128;
+            if diff % g as i128 != 0 {
+                return None;
+            }
+            let l = lcm(m, n)?;
+            // x + m * t ≡ r (mod n), with t = diff/g * p (mod n/g).
+            let step = (n / g) as i128;
+            let t = (diff / g as i128 % step * p as i128).rem_euclid(step);
+            let next = (x as i128 + m as i128 * t).rem_euclid(l as i128);
+            Some((next as i64, l))
+        })
+}
+
+/// An exact fraction in lowest terms with a positive denominator.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    num: i128,
+    den: i128,
+}
+
+impl Ratio {
+    pub const ZERO: Ratio = Ratio { num: 0, den: 1 };
+
+    /// `None` if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Option<Ratio> {
+        if den == 0 {
+            return None;
+        }
+        let g = gcd_u128(num.unsigned_abs(), den.unsigned_abs());
+        let (n, d) = (num.unsigned_abs() / g, den.unsigned_abs() / g);
+        let num = if (num < 0) != (den < 0) {
+            0i128.checked_sub_unsigned(n)?
+        } else {
+            i128::try_from(n).ok()?
+        };
+        Some(Ratio {
+            num,
+            den: i128::try_from(d).ok()?,
+        })
+    }
+
+    pub fn from_int(n: i64) -> Ratio {
+        Ratio {
+            num: n.into(),
+            den: 1,
+        }
+    }
+
+    pub fn numer(self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(self) -> i128 {
+        self.den
+    }
+
+    /// The value, if it is a whole number that fits in an `i64`.
+    pub fn to_integer(self) -> Option<i64> {
+        if self.den == 1 {
+            self.num.try_into().ok()
+        } else {
+            None
+        }
+    }
+
+    pub fn checked_add(self, other: Ratio) -> Option<Ratio> {
+        let num = self
+            .num
+            .checked_mul(other.den)?
+            .checked_add(other.num.checked_mul(self.den)?)?;
+        Ratio::new(num, self.den.checked_mul(other.den)?)
+    }
+
+    pub fn checked_sub(self, other: Ratio) -> Option<Ratio> {
+        self.checked_add(Ratio {
+            num: other.num.checked_neg()?,
+            den: other.den,
+        })
+    }
+
+    pub fn checked_mul(self, other: Ratio) -> Option<Ratio> {
+        Ratio::new(
+            self.num.checked_mul(other.num)?,
+            self.den.checked_mul(other.den)?,
+        )
+    }
+
+    /// `None` when dividing by zero or on overflow.
+    pub fn checked_div(self, other: Ratio) -> Option<Ratio> {
+        Ratio::new(
+            self.num.checked_mul(other.den)?,
+            self.den.checked_mul(other.num)?,
+        )
+    }
+}
+
+impl fmt::Debug for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Solve the square system `a · x = b` exactly by Gaussian elimination over fractions. `None` if
+/// the system is singular (no unique solution) or an intermediate value overflows.
+pub fn solve_linear(a: &[Vec<i64>], b: &[i64]) -> Option<Vec<Ratio>> {
+    let n = b.len();
+    assert!(
+        a.len() == n && a.iter().all(|row| row.len() == n),
+        "system must be square"
+    );
+    let mut m: Vec<Vec<Ratio>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, &rhs)| {
+            row.iter()
+                .chain([&rhs])
+                .map(|&v| Ratio::from_int(v))
+                .collect()
+        })
+        .collect();
+    for col in 0..n {
+        let pivot = (col..n).find(|&r| m[r][col] != Ratio::ZERO)?;
+        m.swap(col, pivot);
+        let pivot_row = m[col].clone();
+        for (r, row) in m.iter_mut().enumerate() {
+            if r == col || row[col] == Ratio::ZERO {
+                continue;
+            }
+            let factor = row[col].checked_div(pivot_row[col])?;
+            for (cell, &p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *cell = cell.checked_sub(factor.checked_mul(p)?)?;
+            }
+        }
+    }
+    (0..n).map(|r| m[r][n].checked_div(m[r][r])).collect()
+}
+
+/// Like `solve_linear`, but only whole-number solutions count.
+pub fn solve_integer(a: &[Vec<i64>], b: &[i64]) -> Option<Vec<i64>> {
+    solve_linear(a, b)?
+        .into_iter()
+        .map(Ratio::to_integer)
+        .collect()
+}
+
+/// The number of decimal digits in `n` (1 for 0).
+pub fn count_digits(n: u64) -> u32 {
+    n.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+/// The decimal digits of `n`, most significant first.
+pub fn digits_of(n: u64) -> Vec<u8> {
+    let mut digits: Vec<u8> = std::iter::successors(Some(n), |&n| (n >= 10).then_some(n / 10))
+        .map(|n| (n % 10) as u8)
+        .collect();
+    digits.reverse();
+    digits
+}
+
+/// The number with decimal `digits` (most significant first); `None` on overflow.
+pub fn from_digits(digits: &[u8]) -> Option<u64> {
+    digits
+        .iter()
+        .try_fold(0u64, |n, &d| n.checked_mul(10)?.checked_add(d.into()))
+}
+
+/// Split `n` before its last `low` digits: `split_digits(253000, 3) == (253, 0)`.
+pub fn split_digits(n: u64, low: u32) -> (u64, u64) {
+    match 10u64.checked_pow(low) {
+        Some(p) => (n / p, n % p),
+        None => (0, n),
+    }
+}
+
+/// The digits of `a` followed by those of `b`: `concat(12, 345) == Some(12345)`.
+pub fn concat(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(10u64.checked_pow(count_digits(b))?)?
+        .checked_add(b)
+}
+
+/// The digits of `n` written `times` times: `repeat_digits(12, 3) == Some(121212)`. Zero times
+/// writes nothing, which reads as `0`.
+pub fn repeat_digits(n: u64, times: u32) -> Option<u64> {
+    if times == 0 {
+        return Some(0);
+    }
+    (1..times).try_fold(n, |acc, _| concat(acc, n))
+}
+
+/// `n` choose `k`; `None` on overflow.
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    // Each step gives C(n, i + 1) exactly, and these only grow while i < k <= n / 2.
+    (0..k).try_fold(1u64, |acc, i| {
+        u64::try_from(acc as u128 * (n - i) as u128 / (i + 1) as u128).ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm_and_modular_arithmetic() {
+        assert_eq!(gcd(-12, 18), Some(6));
+        assert_eq!(gcd_all(&[]), Some(0));
+        assert_eq!(gcd(i64::MIN, 6), Some(2));
+        assert_eq!(gcd(i64::MIN, 0), None);
+        assert_eq!(gcd_all(&[i64::MIN]), None);
+        assert_eq!(extended_gcd(i64::MIN, 0), None);
+        assert_eq!(extended_gcd(i64::MIN, 3).map(|(g, _, _)| g), Some(1));
+        assert_eq!(lcm_all(&[23, 19, 13, 17]), Some(96577));
+        assert_eq!(lcm(i64::MAX, 2), None);
+        assert_eq!(lcm(-4, 6), Some(12));
+        assert_eq!(lcm(i64::MIN, 1), None);
+        assert_eq!(lcm(i64::MIN, i64::MIN), None);
+        assert_eq!(lcm(i64::MIN / 2, -2), Some(i64::MAX / 2 + 1));
+        let (g, x, y) = extended_gcd(240, 46).unwrap();
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_pow(7, 1 << 40, 1_000_000_007), 231_767_222);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        // 2020 day 13: bus IDs 7,13,x,x,59,x,31,19 depart at t + offset.
+        let buses = [(7, 0), (13, 1), (59, 4), (31, 6), (19, 7)];
+        let congruences: Vec<(i64, i64)> = buses.iter().map(|&(id, dt)| (-dt, id)).collect();
+        assert_eq!(crt(&congruences), Some((1_068_781, 3_162_341)));
+        assert_eq!(crt(&[(2, 4), (0, 6)]), Some((6, 12)));
+        assert_eq!(crt(&[(1, 4), (0, 6)]), None);
+    }
+
+    #[test]
+    fn linear_systems() {
+        // 2024 day 13: A moves (94, 34), B moves (22, 67), the prize is at (8400, 5400).
+        let presses = solve_integer(&[vec![94, 22], vec![34, 67]], &[8400, 5400]);
+        assert_eq!(presses, Some(vec![80, 40]));
+        assert_eq!(
+            solve_integer(&[vec![26, 67], vec![66, 21]], &[12748, 12176]),
+            None
+        );
+        let x = solve_linear(&[vec![0, 2, 1], vec![1, 1, 1], vec![2, 0, 3]], &[1, 2, 3]).unwrap();
+        assert_eq!(format!("{x:?}"), "[3/2, 1/2, 0]");
+        assert_eq!(solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 6]), None);
+
+        let parts = |r: Ratio| (r.numer(), r.denom());
+        assert_eq!(Ratio::new(3, -6).map(parts), Some((-1, 2)));
+        assert_eq!(Ratio::new(i128::MIN, 1).map(parts), Some((i128::MIN, 1)));
+        assert_eq!(Ratio::new(i128::MIN, -2).map(parts), Some((1 << 126, 1)));
+        assert_eq!(Ratio::new(i128::MIN, -1), None);
+        assert_eq!(Ratio::new(1, i128::MIN), None);
+    }
+
+    #[test]
+    fn digits_and_binomials() {
+        assert_eq!((count_digits(0), count_digits(253_000)), (1, 6));
+        assert_eq!(digits_of(2024), [2, 0, 2, 4]);
+        assert_eq!(from_digits(&[2, 0, 2, 4]), Some(2024));
+        assert_eq!(split_digits(253_000, 3), (253, 0));
+        assert_eq!(concat(15, 6), Some(156));
+        assert_eq!(concat(u64::MAX, 1), None);
+        assert_eq!(repeat_digits(12, 3), Some(121_212));
+        assert_eq!(repeat_digits(12, 1), Some(12));
+        assert_eq!(repeat_digits(12, 0), Some(0));
+        assert_eq!(binomial(5, 2), Some(10));
+        assert_eq!(binomial(62, 31), Some(465_428_353_255_261_088));
+        assert_eq!(binomial(68, 34), None);
+    }
+}
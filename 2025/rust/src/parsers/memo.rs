@@ -0,0 +1,169 @@
+//! Memoised recursion without a hand-rolled `HashMap` per puzzle.
+//!
+//! `Memo` owns the recursive function and its cache. The function receives the memo back, so it
+//! recurses through `memo.get` and every sub-result is cached:
+//!
+//! ```ignore
+//! // 2024 day 11: how many stones does one stone become after some blinks?
+//! let mut stones = Memo::new(|memo, &(stone, blinks): &(u64, u32)| match blinks {
+//!     0 => 1,
+//!     _ => blink(stone).into_iter().map(|s| memo.get((s, blinks - 1))).sum(),
+//! });
+//! let total: u64 = initial.iter().map(|&s| stones.get((s, 75))).sum();
+//! tracing::debug!("{}", stones.stats());
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::rc::Rc;
+
+type Recursive<'a, K, V> = Rc<dyn Fn(&mut Memo<'a, K, V>, &K) -> V + 'a>;
+
+/// A recursive function together with the cache of its results.
+pub struct Memo<'a, K, V> {
+    f: Recursive<'a, K, V>,
+    cache: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+/// How well a `Memo`'s cache did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let calls = self.hits + self.misses;
+        let rate = if calls == 0 {
+            0.0
+        } else {
+            100.0 * self.hits as f64 / calls as f64
+        };
+        write!(
+            f,
+            "{} calls, {} hits ({rate:.1}%), {} cached",
+            calls, self.hits, self.entries
+        )
+    }
+}
+
+impl<'a, K: Clone + Eq + Hash, V: Clone> Memo<'a, K, V> {
+    pub fn new(f: impl Fn(&mut Memo<'a, K, V>, &K) -> V + 'a) -> Self {
+        Memo {
+            f: Rc::new(f),
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The function's value at `key`, computed at most once.
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let f = Rc::clone(&self.f);
+        let value = f(self, &key);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// The cached value at `key`, without computing it.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forget every cached value and reset the statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{count_digits, split_digits};
+
+    fn blink(stone: u64) -> Vec<u64> {
+        let n = count_digits(stone);
+        if stone == 0 {
+            vec![1]
+        } else if n.is_multiple_of(2) {
+            let (left, right) = split_digits(stone, n / 2);
+            vec![left, right]
+        } else {
+            vec![stone * 2024]
+        }
+    }
+
+    #[test]
+    fn stones() {
+        let mut stones = Memo::new(|memo, &(stone, blinks): &(u64, u32)| match blinks {
+            0 => 1,
+            _ => blink(stone)
+                .into_iter()
+                .map(|s| memo.get((s, blinks - 1)))
+                .sum::<u64>(),
+        });
+        let total: u64 = [125, 17].iter().map(|&s| stones.get((s, 25))).sum();
+        assert_eq!(total, 55312);
+        let stats = stones.stats();
+        assert!(stats.hits > 0);
+        assert_eq!(stats.misses as usize, stats.entries);
+        let value = stones.get((125, 25));
+        assert_eq!(stones.peek(&(125, 25)), Some(&value));
+    }
+
+    #[test]
+    fn towels_borrow_their_environment() {
+        // 2024 day 19: the number of ways to make each design from the towel patterns.
+        let towels = ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"];
+        let mut ways = Memo::new(|memo, design: &&str| -> u64 {
+            if design.is_empty() {
+                return 1;
+            }
+            towels
+                .iter()
+                .filter_map(|t| design.strip_prefix(t))
+                .map(|rest| memo.get(rest))
+                .sum()
+        });
+        let designs = [
+            "brwrr", "bggr", "gbbr", "rrbgbr", "ubwu", "bwurrg", "brgr", "bbrwb",
+        ];
+        let counts: Vec<u64> = designs.iter().map(|d| ways.get(d)).collect();
+        assert_eq!(counts, [2, 1, 4, 6, 0, 1, 2, 0]);
+        assert!(
+            ways.stats()
+                .to_string()
+                .ends_with(&format!("{} cached", ways.len()))
+        );
+        ways.clear();
+        assert!(ways.is_empty());
+    }
+}
@@ -0,0 +1,348 @@
+//! Records parsed from a line pattern.
+//!
+//! `aoc_record!` declares a struct together with a `FromStr` impl driven by a pattern in which
+//! `{field}` placeholders stand for typed fields and everything else must match literally:
+//!
+//! ```ignore
+//! aoc_record! {
+//!     #[aoc(pattern = "p={x},{y} v={dx},{dy}")]
+//!     #[derive(Debug, Clone, Copy)]
+//!     struct Robot { x: i64, y: i64, dx: i64, dy: i64 }
+//! }
+//!
+//! let robots = try_parse_text(input, Robot::from_str, lines)?;
+//! ```
+//!
+//! `FromStr::Err` is `ParseError`, so `Robot::from_str` plugs straight into `try_parse_text` and
+//! `lines_of` section parsers, and errors point at the offending characters.
+//!
+//! The pattern is checked against the field list at compile time (`check_pattern`), and split
+//! into pieces once, on first use.
+
+use super::error::ParseError;
+
+/// One piece of a pattern: literal text or a `{name}` placeholder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Piece<'p> {
+    Literal(&'p str),
+    Field(&'p str),
+}
+
+/// A line pattern split into literal text and `{name}` placeholders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern<'p> {
+    pieces: Vec<Piece<'p>>,
+}
+
+impl<'p> Pattern<'p> {
+    /// Split `pattern` into pieces. Panics on an unclosed `{` or adjacent placeholders, which
+    /// `check_pattern` rejects at compile time for `aoc_record!` patterns.
+    pub fn new(pattern: &'p str) -> Self {
+        let mut pieces = Vec::new();
+        let mut rest = pattern;
+        while let Some(open) = rest.find('{') {
+            let close = rest[open..]
+                .find('}')
+                .unwrap_or_else(|| panic!("unclosed '{{' in pattern {pattern:?}"))
+                + open;
+            if open > 0 {
+                pieces.push(Piece::Literal(&rest[..open]));
+            }
+            let name = &rest[open + 1..close];
+            if let Some(Piece::Field(prev)) = pieces.last() {
+                panic!("placeholders `{prev}` and `{name}` are adjacent in {pattern:?}");
+            }
+            pieces.push(Piece::Field(name));
+            rest = &rest[close + 1..];
+        }
+        if !rest.is_empty() {
+            pieces.push(Piece::Literal(rest));
+        }
+        Pattern { pieces }
+    }
+
+    /// Match `line`, returning each placeholder's name and the slice of `line` it covers. A
+    /// placeholder extends to the next occurrence of the literal text after it (or to the end of
+    /// the line).
+    pub fn match_line<'l>(&self, line: &'l str) -> Result<Vec<(&'p str, &'l str)>, ParseError> {
+        let mut fields = Vec::new();
+        let mut rest = line.trim_end();
+        for (i, piece) in self.pieces.iter().enumerate() {
+            match *piece {
+                Piece::Literal(lit) => match rest.strip_prefix(lit) {
+                    Some(after) => rest = after,
+                    None => {
+                        let n = lit.len().min(rest.len());
+                        let n = (0..=n)
+                            .rev()
+                            .find(|&n| rest.is_char_boundary(n))
+                            .unwrap_or(0);
+                        return Err(ParseError::at(
+                            line,
+                            &rest[..n],
+                            format!("expected {lit:?}"),
+                        ));
+                    }
+                },
+                Piece::Field(name) => {
+                    let end = match self.pieces.get(i + 1) {
+                        Some(Piece::Literal(lit)) => rest.find(lit).ok_or_else(|| {
+                            ParseError::at(line, rest, format!("expected {lit:?} after `{name}`"))
+                        })?,
+                        _ => rest.len(),
+                    };
+                    fields.push((name, &rest[..end]));
+                    rest = &rest[end..];
+                }
+            }
+        }
+        if !rest.is_empty() {
+            return Err(ParseError::at(line, rest, "expected end of line"));
+        }
+        Ok(fields)
+    }
+}
+
+/// Match `line` against a one-off `pattern`; see `Pattern::match_line`.
+pub fn match_pattern<'p, 'l>(
+    pattern: &'p str,
+    line: &'l str,
+) -> Result<Vec<(&'p str, &'l str)>, ParseError> {
+    Pattern::new(pattern).match_line(line)
+}
+
+/// Check that `pattern` is well formed and that its placeholders are exactly `fields`: every `{`
+/// is closed, no placeholder is empty or directly follows another, each placeholder names a
+/// field and each field has a placeholder. `aoc_record!` evaluates this in a `const`, so a bad
+/// pattern fails the build.
+pub const fn check_pattern(pattern: &str, fields: &[&str]) -> Result<(), &'static str> {
+    let p = pattern.as_bytes();
+    let mut i = 0;
+    let mut after_placeholder = false;
+    while i < p.len() {
+        if p[i] != b'{' {
+            after_placeholder = false;
+            i += 1;
+            continue;
+        }
+        let mut close = i + 1;
+        while close < p.len() && p[close] != b'}' {
+            close += 1;
+        }
+        if close == p.len() {
+            return Err("unclosed '{' in pattern");
+        }
+        if close == i + 1 {
+            return Err("empty placeholder in pattern");
+        }
+        if after_placeholder {
+            return Err("adjacent placeholders in pattern");
+        }
+        let mut j = 0;
+        while j < fields.len() && !placeholder_at(p, i, fields[j].as_bytes()) {
+            j += 1;
+        }
+        if j == fields.len() {
+            return Err("pattern placeholder names no field of the record");
+        }
+        after_placeholder = true;
+        i = close + 1;
+    }
+    let mut j = 0;
+    while j < fields.len() {
+        let mut i = 0;
+        while i < p.len() && !placeholder_at(p, i, fields[j].as_bytes()) {
+            i += 1;
+        }
+        if i == p.len() {
+            return Err("record field has no placeholder in pattern");
+        }
+        j += 1;
+    }
+    Ok(())
+}
+
+/// Is `{name}` at byte `i` of `pattern`?
+const fn placeholder_at(pattern: &[u8], i: usize, name: &[u8]) -> bool {
+    if i + name.len() + 2 > pattern.len()
+        || pattern[i] != b'{'
+        || pattern[i + name.len() + 1] != b'}'
+    {
+        return false;
+    }
+    let mut k = 0;
+    while k < name.len() {
+        if pattern[i + 1 + k] != name[k] {
+            return false;
+        }
+        k += 1;
+    }
+    true
+}
+
+/// Parse the placeholder `name` from matched `fields` as a `T`.
+pub fn pattern_field<T: std::str::FromStr>(
+    line: &str,
+    fields: &[(&str, &str)],
+    name: &str,
+) -> Result<T, ParseError> {
+    let (_, text) = fields
+        .iter()
+        .find(|(n, _)| *n == name)
+        .unwrap_or_else(|| panic!("pattern has no placeholder for field `{name}`"));
+    ParseError::parse_field(
+        line,
+        text,
+        &format!("{} for `{name}`", std::any::type_name::<T>()),
+    )
+}
+
+/// Declare a struct parsed from a line pattern; see the module docs.
+#[allow(unused_macros)]
+macro_rules! aoc_record {
+    (
+        #[aoc(pattern = $pattern:literal)]
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($fvis:vis $field:ident : $ty:ty),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($fvis $field: $ty),+
+        }
+
+        impl $name {
+            /// The line pattern this record is parsed from.
+            #[allow(dead_code)]
+            pub const PATTERN: &'static str = $pattern;
+        }
+
+        const _: () = match $crate::parsers::check_pattern($pattern, &[$(stringify!($field)),+]) {
+            Ok(()) => (),
+            Err(problem) => panic!("{}", problem),
+        };
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::parsers::ParseError;
+
+            fn from_str(line: &str) -> ::std::result::Result<Self, Self::Err> {
+                static PATTERN: ::once_cell::sync::Lazy<$crate::parsers::Pattern<'static>> =
+                    ::once_cell::sync::Lazy::new(|| $crate::parsers::Pattern::new($pattern));
+                let fields = PATTERN.match_line(line)?;
+                Ok($name {
+                    $($field: $crate::parsers::pattern_field(line, &fields, stringify!($field))?),+
+                })
+            }
+        }
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use aoc_record;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{lines, try_parse_text};
+    use std::str::FromStr;
+
+    aoc_record! {
+        #[aoc(pattern = "p={x},{y} v={dx},{dy}")]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Robot {
+            x: i64,
+            y: i64,
+            dx: i64,
+            dy: i64,
+        }
+    }
+
+    aoc_record! {
+        #[aoc(pattern = "{name} can fly {speed} km/s for {fly} seconds")]
+        #[derive(Debug, PartialEq)]
+        struct Reindeer {
+            name: String,
+            speed: u32,
+            fly: u32,
+        }
+    }
+
+    #[test]
+    fn robots() {
+        let robots =
+            try_parse_text("p=0,4 v=3,-3\np=6,3 v=-1,-3\n", Robot::from_str, lines).unwrap();
+        assert_eq!(
+            robots[1],
+            Robot {
+                x: 6,
+                y: 3,
+                dx: -1,
+                dy: -3
+            }
+        );
+        assert_eq!(Robot::PATTERN, "p={x},{y} v={dx},{dy}");
+    }
+
+    #[test]
+    fn strings_and_literal_tails() {
+        let comet: Reindeer = "Comet can fly 14 km/s for 10 seconds".parse().unwrap();
+        assert_eq!(comet.name, "Comet");
+        assert_eq!((comet.speed, comet.fly), (14, 10));
+    }
+
+    #[test]
+    fn precise_errors() {
+        let err = Robot::from_str("p=0,4 v=3,x").unwrap_err();
+        assert_eq!(err.column, 11);
+        assert_eq!(err.message, "expected i64 for `dy`");
+        let err = Robot::from_str("p=0,4 w=3,3").unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (5, "expected \" v=\" after `y`")
+        );
+        let err = Robot::from_str("q=0,4 v=3,3").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (1, "q="));
+        let err = Robot::from_str("p=0,4").unwrap_err();
+        assert_eq!(err.message, "expected \" v=\" after `y`");
+        let err =
+            try_parse_text("p=1,1 v=1,1\np=1,1 v=1,1 extra", Robot::from_str, lines).unwrap_err();
+        assert!(err.to_string().contains("line 2, column 11"));
+    }
+
+    #[test]
+    fn patterns_are_checked_against_fields() {
+        let robot = ["x", "y", "dx", "dy"];
+        assert_eq!(check_pattern("p={x},{y} v={dx},{dy}", &robot), Ok(()));
+        assert_eq!(
+            check_pattern("p={x},{y v={dx},{dy}", &robot),
+            Err("pattern placeholder names no field of the record")
+        );
+        assert_eq!(
+            check_pattern("p={x},{y} v={dx},{dy", &robot),
+            Err("unclosed '{' in pattern")
+        );
+        assert_eq!(
+            check_pattern("p={x}{y} v={dx},{dy}", &robot),
+            Err("adjacent placeholders in pattern")
+        );
+        assert_eq!(
+            check_pattern("p={x},{y} v={dx},{dz}", &robot),
+            Err("pattern placeholder names no field of the record")
+        );
+        assert_eq!(
+            check_pattern("p={x},{y} v={dx}", &robot),
+            Err("record field has no placeholder in pattern")
+        );
+        assert_eq!(
+            check_pattern("p={x},{} v={dx},{dy}", &robot),
+            Err("empty placeholder in pattern")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "adjacent")]
+    fn one_off_patterns_reject_adjacent_placeholders() {
+        let _ = match_pattern("{a}{b}", "12");
+    }
+}
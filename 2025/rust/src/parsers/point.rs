@@ -0,0 +1,320 @@
+//! Typed points/vectors with arithmetic, plus compass directions with turning.
+//!
+//! `Grid` keeps the plain `(i32, i32)` tuple `Point` for indexing; `Point2<i32>` converts to and
+//! from it with `From`/`Into`, so simulations can do vector maths and still index a grid:
+//!
+//! ```ignore
+//! let mut pos = Point2::from(start);
+//! let mut dir = Direction::Up;
+//! while grid.get((pos + dir.delta()).into()) == Some('#') {
+//!     dir = dir.turn_right();
+//! }
+//! pos += dir.delta();
+//! ```
+
+use color_eyre::eyre::{Report, Result, bail};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Signed integer types usable as point coordinates.
+pub trait Coord:
+    Copy
+    + Default
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {$(
+        impl Coord for $t {
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        }
+    )*};
+}
+
+impl_coord!(i8, i16, i32, i64, i128, isize);
+
+/// Defines a point type with named fields and component-wise arithmetic.
+macro_rules! point_type {
+    ($(#[$doc:meta])* $name:ident { $($field:ident),+ }, $tuple:ty) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name<T> {
+            $(pub $field: T),+
+        }
+
+        impl<T: Coord> $name<T> {
+            pub const fn new($($field: T),+) -> Self {
+                $name { $($field),+ }
+            }
+
+            /// Sum of the absolute components (taxicab length).
+            pub fn manhattan_len(self) -> T {
+                T::default() $(+ self.$field.abs())+
+            }
+
+            /// Largest absolute component (king-move length).
+            pub fn chebyshev_len(self) -> T {
+                let mut m = T::default();
+                $(m = m.max(self.$field.abs());)+
+                m
+            }
+
+            /// Taxicab distance to `other`.
+            pub fn manhattan(self, other: Self) -> T {
+                (self - other).manhattan_len()
+            }
+
+            /// King-move distance to `other`.
+            pub fn chebyshev(self, other: Self) -> T {
+                (self - other).chebyshev_len()
+            }
+
+            /// Each component clamped to -1, 0 or 1 (a unit step towards a target).
+            pub fn signum(self) -> Self {
+                $name { $($field: self.$field.signum()),+ }
+            }
+        }
+
+        impl<T: Coord> Add for $name<T> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                $name { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T: Coord> Sub for $name<T> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                $name { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        /// Scale by a scalar.
+        impl<T: Coord> Mul<T> for $name<T> {
+            type Output = Self;
+            fn mul(self, k: T) -> Self {
+                $name { $($field: self.$field * k),+ }
+            }
+        }
+
+        impl<T: Coord> Neg for $name<T> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                $name { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Coord> AddAssign for $name<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Coord> SubAssign for $name<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<T> From<$tuple> for $name<T> {
+            fn from(($($field),+): $tuple) -> Self {
+                $name { $($field),+ }
+            }
+        }
+
+        impl<T> From<$name<T>> for $tuple {
+            fn from(p: $name<T>) -> Self {
+                ($(p.$field),+)
+            }
+        }
+    };
+}
+
+point_type!(
+    /// A 2D point or vector. With the grid conventions `x` grows right and `y` grows down.
+    Point2 { x, y },
+    (T, T)
+);
+point_type!(
+    /// A 3D point or vector, for voxel puzzles.
+    Point3 { x, y, z },
+    (T, T, T)
+);
+point_type!(
+    /// A 4D point or vector (e.g. space-time constellations).
+    Point4 { x, y, z, w },
+    (T, T, T, T)
+);
+
+/// A compass direction on a grid where `y` grows downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from `Up`, matching the order of the compass.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The unit step in this direction.
+    pub fn delta(self) -> Point2<i32> {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::Down => Point2::new(0, 1),
+            Direction::Left => Point2::new(-1, 0),
+        }
+    }
+
+    /// The unit step as a grid `Point`.
+    pub fn offset(self) -> (i32, i32) {
+        self.delta().into()
+    }
+
+    /// Turn 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Turn 90° counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    /// Parse an arrow (`^>v<`), compass letter (`NESW`) or `UDLR` letter, in either case.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            '^' | 'N' | 'U' => Some(Direction::Up),
+            '>' | 'E' | 'R' => Some(Direction::Right),
+            'V' | 'S' | 'D' => Some(Direction::Down),
+            '<' | 'W' | 'L' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// The arrow used for this direction in puzzle inputs.
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = Report;
+
+    fn try_from(c: char) -> Result<Self> {
+        match Direction::from_char(c) {
+            Some(d) => Ok(d),
+            None => bail!("not a direction: {c:?}"),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Report;
+
+    /// A single direction character, or a word such as `up` or `north`.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => match s.to_ascii_lowercase().as_str() {
+                "up" | "north" => Ok(Direction::Up),
+                "right" | "east" => Ok(Direction::Right),
+                "down" | "south" => Ok(Direction::Down),
+                "left" | "west" => Ok(Direction::Left),
+                _ => bail!("not a direction: {s:?}"),
+            },
+        }
+    }
+}
+
+impl Add<Direction> for Point2<i32> {
+    type Output = Self;
+    fn add(self, d: Direction) -> Self {
+        self + d.delta()
+    }
+}
+
+impl AddAssign<Direction> for Point2<i32> {
+    fn add_assign(&mut self, d: Direction) {
+        *self = *self + d;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_and_distances() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(b - a, Point2::new(3, -4));
+        assert_eq!(-a * 3, Point2::new(-3, -6));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!((b - a).signum(), Point2::new(1, -1));
+        let p: (i32, i32) = (a + Direction::Left).into();
+        assert_eq!(p, (0, 2));
+
+        let c = Point3::new(1i64, 1, 1);
+        assert_eq!((c * 2 - Point3::from((0, 0, 5))).manhattan_len(), 7);
+        assert_eq!(Point4::new(0, 3, 0, -4).chebyshev_len(), 4);
+    }
+
+    #[test]
+    fn directions() {
+        let d: Direction = "^".parse().unwrap();
+        assert_eq!(d, Direction::Up);
+        assert_eq!(d.turn_right(), Direction::Right);
+        assert_eq!(d.turn_left(), Direction::Left);
+        assert_eq!(d.reverse(), Direction::Down);
+        assert_eq!(
+            "NESW".chars().map(Direction::from_char).collect::<Vec<_>>(),
+            "URDL".chars().map(Direction::from_char).collect::<Vec<_>>()
+        );
+        assert_eq!(Direction::try_from('v').unwrap().arrow(), 'v');
+        assert_eq!("west".parse::<Direction>().unwrap(), Direction::Left);
+        assert!("x".parse::<Direction>().is_err());
+        let mut p = Point2::new(0, 0);
+        for d in Direction::ALL {
+            p += d;
+        }
+        assert_eq!(p, Point2::default());
+    }
+}
@@ -0,0 +1,282 @@
+//! Recording simulations as animated GIFs or asciinema `.cast` files.
+//!
+//! ```ignore
+//! let mut rec = GridRecorder::new().every(10).max_frames(500).colour('#', Rgb::GREY);
+//! for _ in 0..steps {
+//!     step(&mut robots);
+//!     rec.capture_points(&room, robots.iter().map(|r| r.pos), '@');
+//! }
+//! rec.write_gif("day14.gif", 4)?;
+//! ```
+
+use super::grid::{Grid, Point};
+use super::render::{Renderer, Rgb};
+use color_eyre::eyre::{Result, bail};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// One recorded frame: the grid as it was plus any highlighted points.
+struct Frame {
+    grid: Grid,
+    highlights: Vec<(Vec<Point>, Rgb)>,
+}
+
+impl Frame {
+    fn renderer<'a>(&'a self, palette: &HashMap<char, Rgb>) -> Renderer<'a> {
+        let mut renderer = Renderer::new(&self.grid);
+        for (&ch, &rgb) in palette {
+            renderer = renderer.colour(ch, rgb);
+        }
+        for (points, rgb) in &self.highlights {
+            renderer = renderer.highlight(points.iter().copied(), *rgb);
+        }
+        renderer
+    }
+}
+
+/// Collects frames of a character grid during a simulation. Only every `every`th capture is
+/// kept, and captures beyond `max_frames` kept frames are dropped.
+pub struct GridRecorder {
+    every: usize,
+    max_frames: usize,
+    delay_ms: u32,
+    palette: HashMap<char, Rgb>,
+    captures: usize,
+    frames: Vec<Frame>,
+}
+
+impl Default for GridRecorder {
+    fn default() -> Self {
+        GridRecorder::new()
+    }
+}
+
+impl GridRecorder {
+    pub fn new() -> Self {
+        GridRecorder {
+            every: 1,
+            max_frames: 1000,
+            delay_ms: 100,
+            palette: HashMap::new(),
+            captures: 0,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Keep one capture in `n` (the first capture is always kept).
+    pub fn every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    /// Stop keeping frames once `n` have been recorded.
+    pub fn max_frames(mut self, n: usize) -> Self {
+        self.max_frames = n;
+        self
+    }
+
+    /// Time each frame is shown for.
+    pub fn delay_ms(mut self, ms: u32) -> Self {
+        self.delay_ms = ms;
+        self
+    }
+
+    /// Colour cells holding `ch`, as for `Renderer::colour`.
+    pub fn colour(mut self, ch: char, rgb: Rgb) -> Self {
+        self.palette.insert(ch, rgb);
+        self
+    }
+
+    /// Number of frames kept so far.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Whether the next capture would be kept. Lets callers skip building expensive frames.
+    pub fn wants_frame(&self) -> bool {
+        self.captures.is_multiple_of(self.every) && self.frames.len() < self.max_frames
+    }
+
+    fn push(&mut self, frame: impl FnOnce() -> Frame) -> bool {
+        let keep = self.wants_frame();
+        self.captures += 1;
+        if keep {
+            self.frames.push(frame());
+        }
+        keep
+    }
+
+    /// Capture `grid` as it is now. Returns whether the frame was kept.
+    pub fn capture(&mut self, grid: &Grid) -> bool {
+        self.push(|| Frame {
+            grid: grid.clone(),
+            highlights: Vec::new(),
+        })
+    }
+
+    /// Capture `background` with `glyph` drawn at each of `points` (robots, a guard...).
+    pub fn capture_points(
+        &mut self,
+        background: &Grid,
+        points: impl IntoIterator<Item = Point>,
+        glyph: char,
+    ) -> bool {
+        self.push(|| {
+            let mut grid = background.clone();
+            for p in points {
+                if grid.in_range(p) {
+                    grid.set(p, glyph);
+                }
+            }
+            Frame {
+                grid,
+                highlights: Vec::new(),
+            }
+        })
+    }
+
+    /// Capture `grid` with `points` highlighted in `rgb` (a path so far, visited cells...).
+    pub fn capture_highlighted(
+        &mut self,
+        grid: &Grid,
+        points: impl IntoIterator<Item = Point>,
+        rgb: Rgb,
+    ) -> bool {
+        self.push(|| Frame {
+            grid: grid.clone(),
+            highlights: vec![(points.into_iter().collect(), rgb)],
+        })
+    }
+
+    /// Write the frames as a looping animated GIF, each cell drawn as a `cell_size` square.
+    /// Frames are drawn at the size of the first frame.
+    pub fn write_gif(&self, path: impl AsRef<Path>, cell_size: u32) -> Result<()> {
+        let Some(first) = self.frames.first() else {
+            bail!("no frames recorded");
+        };
+        let (w, h, _) = first.renderer(&self.palette).pixels(cell_size);
+        if w > u16::MAX as u32 || h > u16::MAX as u32 {
+            bail!("{w}x{h} is too large for a GIF");
+        }
+        let (w, h) = (w as u16, h as u16);
+        let mut encoder = gif::Encoder::new(BufWriter::new(File::create(path)?), w, h, &[])?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for frame in &self.frames {
+            let (fw, fh, data) = frame.renderer(&self.palette).pixels(cell_size);
+            if (fw, fh) != (w as u32, h as u32) {
+                bail!("frame is {fw}x{fh} pixels but the first frame is {w}x{h}");
+            }
+            let mut image = gif::Frame::from_rgb_speed(w, h, &data, 10);
+            // GIF delays are in hundredths of a second.
+            image.delay = (self.delay_ms / 10).min(u16::MAX as u32) as u16;
+            encoder.write_frame(&image)?;
+        }
+        Ok(())
+    }
+
+    /// The frames as an asciinema v2 recording: a JSON header line then one output event per
+    /// frame, each clearing the screen and drawing the ANSI rendering.
+    pub fn cast(&self) -> String {
+        let (w, h) = self.frames.iter().fold((0, 0), |(w, h), f| {
+            (w.max(f.grid.size.0), h.max(f.grid.size.1))
+        });
+        let mut out = format!("{{\"version\": 2, \"width\": {w}, \"height\": {h}}}\n");
+        for (i, frame) in self.frames.iter().enumerate() {
+            let time = i as f64 * self.delay_ms as f64 / 1000.0;
+            let text = format!(
+                "\x1b[H\x1b[2J{}",
+                frame.renderer(&self.palette).ansi().replace('\n', "\r\n")
+            );
+            let _ = writeln!(out, "[{time:.3}, \"o\", \"{}\"]", json_escape(&text));
+        }
+        out
+    }
+
+    /// Write the frames as an asciinema `.cast` file (play with `asciinema play`).
+    pub fn write_cast(&self, path: impl AsRef<Path>) -> Result<()> {
+        if self.frames.is_empty() {
+            bail!("no frames recorded");
+        }
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(self.cast().as_bytes())?;
+        Ok(())
+    }
+}
+
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{DIRECTIONS4, Missing};
+
+    #[test]
+    fn frame_skipping_and_cap() {
+        let room = Grid::filled((3, 2), '.', &DIRECTIONS4, Missing::Error);
+        let mut rec = GridRecorder::new().every(2).max_frames(3);
+        let kept: Vec<bool> = (0..10)
+            .map(|x| rec.capture_points(&room, [(x % 3, 0)], '@'))
+            .collect();
+        assert_eq!(
+            kept,
+            [
+                true, false, true, false, true, false, false, false, false, false
+            ]
+        );
+        assert_eq!(rec.len(), 3);
+        assert_eq!(rec.frames[1].grid.to_string(), "..@\n...\n");
+    }
+
+    #[test]
+    fn cast_format() {
+        let grid = Grid::from_str("#.\n.#");
+        let mut rec = GridRecorder::new().delay_ms(250).colour('#', Rgb::RED);
+        rec.capture(&grid);
+        rec.capture_highlighted(&grid, [(1, 0)], Rgb::BLUE);
+        let cast = rec.cast();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines[0], r#"{"version": 2, "width": 2, "height": 2}"#);
+        assert!(lines[1].starts_with(r#"[0.000, "o", "\u001b[H\u001b[2J\u001b[38;2;220;50;47m#"#));
+        assert!(lines[2].starts_with("[0.250, "));
+        assert!(lines[2].contains(r"\u001b[48;2;38;139;210m.\u001b[0m\r\n"));
+    }
+
+    #[test]
+    fn gif_export() {
+        let grid = Grid::from_str("#.\n.#");
+        let mut rec = GridRecorder::new();
+        assert!(
+            rec.write_gif(std::env::temp_dir().join("unused.gif"), 1)
+                .is_err()
+        );
+        rec.capture(&grid);
+        rec.capture(&grid.flip_horizontal());
+        let path = std::env::temp_dir().join("aoc25-record-test.gif");
+        rec.write_gif(&path, 2).unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(b"GIF89a"));
+        let _ = std::fs::remove_file(path);
+    }
+}
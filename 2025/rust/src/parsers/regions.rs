@@ -0,0 +1,214 @@
+//! Flood fill, connected regions and their shape measures, and "peel until stable" processes.
+
+use super::grid::{DIRECTIONS4, Grid, Point, add2};
+use std::collections::HashSet;
+
+/// A connected set of grid points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub points: HashSet<Point>,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.points.contains(&p)
+    }
+
+    /// Number of unit cell edges between the region and the outside (4-neighbourhood).
+    pub fn perimeter(&self) -> usize {
+        self.points
+            .iter()
+            .flat_map(|&p| DIRECTIONS4.iter().map(move |&d| add2(p, d)))
+            .filter(|q| !self.points.contains(q))
+            .count()
+    }
+
+    /// Number of straight fence sides, counted as the number of corners (convex and concave).
+    pub fn sides(&self) -> usize {
+        // Pairs of orthogonal directions around each corner of a cell.
+        let turns = [(0, 1), (1, 2), (2, 3), (3, 0)];
+        self.points
+            .iter()
+            .map(|&p| {
+                turns
+                    .iter()
+                    .filter(|&&(a, b)| {
+                        let (da, db) = (DIRECTIONS4[a], DIRECTIONS4[b]);
+                        let side_a = self.contains(add2(p, da));
+                        let side_b = self.contains(add2(p, db));
+                        let diagonal = self.contains(add2(p, (da.0 + db.0, da.1 + db.1)));
+                        (!side_a && !side_b) || (side_a && side_b && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Inclusive `(min, max)` corners of the smallest box holding the region.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        let xs = self.points.iter().map(|p| p.0);
+        let ys = self.points.iter().map(|p| p.1);
+        Some((
+            (xs.clone().min()?, ys.clone().min()?),
+            (xs.max()?, ys.max()?),
+        ))
+    }
+}
+
+impl<T> Grid<T> {
+    /// All points connected to `start` (by this grid's directions) whose values satisfy
+    /// `connected(from, to)` along the way.
+    pub fn flood_fill(&self, start: Point, connected: impl Fn(&T, &T) -> bool) -> Region {
+        let mut points = HashSet::new();
+        if self.get_ref(start).is_none() {
+            return Region { points };
+        }
+        let mut stack = vec![start];
+        points.insert(start);
+        while let Some(p) = stack.pop() {
+            for q in self.moves(p, &connected) {
+                if points.insert(q) {
+                    stack.push(q);
+                }
+            }
+        }
+        Region { points }
+    }
+
+    /// Connected components of the cells satisfying `pred`.
+    pub fn regions_where(&self, pred: impl Fn(&T) -> bool) -> Vec<Region> {
+        self.components(|a, b| pred(a) && pred(b), &pred)
+    }
+
+    /// Connected components of cells holding equal values (e.g. garden plots of one plant).
+    pub fn regions(&self) -> Vec<Region>
+    where
+        T: PartialEq,
+    {
+        self.components(|a, b| a == b, |_| true)
+    }
+
+    fn components(
+        &self,
+        connected: impl Fn(&T, &T) -> bool,
+        include: impl Fn(&T) -> bool,
+    ) -> Vec<Region> {
+        let mut seen: HashSet<Point> = HashSet::new();
+        let mut regions = Vec::new();
+        let mut starts: Vec<Point> = self.find_where(&include);
+        starts.sort_unstable_by_key(|&(x, y)| (y, x));
+        for p in starts {
+            if !seen.contains(&p) {
+                let region = self.flood_fill(p, &connected);
+                seen.extend(&region.points);
+                regions.push(region);
+            }
+        }
+        regions
+    }
+
+    /// Repeatedly clear every cell for which `removable(grid, p)` holds, setting it to `empty`,
+    /// until a round removes nothing. Rounds are synchronous: each round's test sees the grid as
+    /// it was at the start of the round. After the first round only neighbours of removed cells
+    /// are re-tested, and a removed cell is never tested again, so `removable` need not check
+    /// whether `p` is already empty. Returns the points removed in each round.
+    pub fn peel(&mut self, removable: impl Fn(&Self, Point) -> bool, empty: T) -> Vec<Vec<Point>>
+    where
+        T: Clone,
+    {
+        let mut rounds = Vec::new();
+        let mut gone: HashSet<Point> = HashSet::new();
+        let mut candidates: HashSet<Point> = self.iter().map(|(p, _)| p).collect();
+        loop {
+            let mut removed: Vec<Point> = candidates
+                .iter()
+                .copied()
+                .filter(|&p| removable(self, p))
+                .collect();
+            if removed.is_empty() {
+                return rounds;
+            }
+            removed.sort_unstable_by_key(|&(x, y)| (y, x));
+            for &p in &removed {
+                self.set(p, empty.clone());
+            }
+            gone.extend(&removed);
+            candidates = removed
+                .iter()
+                .flat_map(|&p| self.neighbours(p))
+                .filter(|&q| self.contains(q) && !gone.contains(&q))
+                .collect();
+            rounds.push(removed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024 day 12's first example garden.
+    const GARDEN: &str = "AAAA\nBBCD\nBBCC\nEEEC";
+
+    #[test]
+    fn garden_prices() {
+        let regions = Grid::from_str(GARDEN).regions();
+        assert_eq!(regions.len(), 5);
+        let by_perimeter: usize = regions.iter().map(|r| r.area() * r.perimeter()).sum();
+        let by_sides: usize = regions.iter().map(|r| r.area() * r.sides()).sum();
+        assert_eq!(by_perimeter, 140);
+        assert_eq!(by_sides, 80);
+    }
+
+    #[test]
+    fn sides_count_concave_corners() {
+        // An E shape: 12 sides.
+        let grid = Grid::from_str("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE");
+        let e = &grid.regions_where(|&c| c == 'E')[0];
+        assert_eq!(e.area(), 17);
+        assert_eq!(e.sides(), 12);
+        assert_eq!(e.bounding_box(), Some(((0, 0), (4, 4))));
+    }
+
+    #[test]
+    fn regions_where_splits_components() {
+        let grid = Grid::from_str("#.#\n#.#\n..#");
+        let walls = grid.regions_where(|&c| c == '#');
+        assert_eq!(
+            walls.iter().map(Region::area).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn peel_until_stable() {
+        // Cells with fewer than two '#' neighbours erode away, leaving the 2x2 block.
+        let mut grid = Grid::from_str("##...\n##...\n..#..\n...#.");
+        let rounds = grid.peel(
+            |g, p| {
+                g.get(p) == Some('#')
+                    && g.neighbour_contents(p)
+                        .iter()
+                        .filter(|&&c| c == '#')
+                        .count()
+                        < 2
+            },
+            '.',
+        );
+        assert_eq!(rounds, vec![vec![(2, 2), (3, 3)]]);
+        assert_eq!(grid.find_all(['#']).len(), 4);
+    }
+
+    #[test]
+    fn peel_never_retests_removed_cells() {
+        // The test doesn't look at the cell itself, so emptied cells would stay removable.
+        let mut grid = Grid::from_str("#.#\n...\n#.#");
+        let rounds = grid.peel(|g, p| !g.neighbour_contents(p).contains(&'#'), ' ');
+        assert_eq!(rounds.iter().map(Vec::len).sum::<usize>(), 9);
+        assert!(grid.find_all(['#', '.']).is_empty());
+    }
+}
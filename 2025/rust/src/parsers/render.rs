@@ -0,0 +1,215 @@
+//! Grid rendering for debugging: plain text, ANSI colour terminal output with highlight overlays,
+//! and PNG/PPM image export.
+//!
+//! ```ignore
+//! Renderer::new(&grid)
+//!     .colour('#', Rgb::GREY)
+//!     .highlight(path.iter().copied(), Rgb::RED)
+//!     .highlight(visited, Rgb::BLUE)
+//!     .write_png("day16.png", 4)?;
+//! ```
+
+use super::grid::{Grid, Point};
+use color_eyre::eyre::Result;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// A 24-bit colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(133, 153, 0);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(181, 137, 0);
+    pub const MAGENTA: Rgb = Rgb(211, 54, 130);
+    pub const CYAN: Rgb = Rgb(42, 161, 152);
+
+    /// The `i`th of a cycle of distinct overlay colours.
+    pub fn nth(i: usize) -> Rgb {
+        const CYCLE: [Rgb; 6] = [
+            Rgb::RED,
+            Rgb::GREEN,
+            Rgb::BLUE,
+            Rgb::YELLOW,
+            Rgb::MAGENTA,
+            Rgb::CYAN,
+        ];
+        CYCLE[i % CYCLE.len()]
+    }
+}
+
+/// Renders a character grid with a per-char palette and highlighted point sets on top.
+/// Later highlights win over earlier ones.
+pub struct Renderer<'a> {
+    grid: &'a Grid,
+    palette: HashMap<char, Rgb>,
+    highlights: HashMap<Point, Rgb>,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(grid: &'a Grid) -> Self {
+        Renderer {
+            grid,
+            palette: HashMap::new(),
+            highlights: HashMap::new(),
+        }
+    }
+
+    /// Colour cells holding `ch` (foreground in the terminal, fill in images).
+    pub fn colour(mut self, ch: char, rgb: Rgb) -> Self {
+        self.palette.insert(ch, rgb);
+        self
+    }
+
+    /// Highlight `points` (a path, visited cells, antinodes...) with `rgb` as a background.
+    pub fn highlight(mut self, points: impl IntoIterator<Item = Point>, rgb: Rgb) -> Self {
+        self.highlights.extend(points.into_iter().map(|p| (p, rgb)));
+        self
+    }
+
+    fn rows(&self) -> Vec<Vec<char>> {
+        self.grid.to_rows(None, None)
+    }
+
+    /// Image colour of a cell: its highlight, else its palette colour, else black for empty-looking
+    /// cells and light grey for anything else.
+    fn fill(&self, p: Point, ch: char) -> Rgb {
+        if let Some(&rgb) = self.highlights.get(&p) {
+            return rgb;
+        }
+        match self.palette.get(&ch) {
+            Some(&rgb) => rgb,
+            None if ch == '.' || ch == ' ' => Rgb::BLACK,
+            None => Rgb(200, 200, 200),
+        }
+    }
+
+    /// The grid as text with 24-bit ANSI colour escapes, one line per row.
+    pub fn ansi(&self) -> String {
+        let mut out = String::new();
+        for (y, row) in self.rows().into_iter().enumerate() {
+            for (x, ch) in row.into_iter().enumerate() {
+                let p = (x as i32, y as i32);
+                let fg = self.palette.get(&ch);
+                let bg = self.highlights.get(&p);
+                if let Some(Rgb(r, g, b)) = fg {
+                    out.push_str(&format!("\x1b[38;2;{r};{g};{b}m"));
+                }
+                if let Some(Rgb(r, g, b)) = bg {
+                    out.push_str(&format!("\x1b[48;2;{r};{g};{b}m"));
+                }
+                out.push(ch);
+                if fg.is_some() || bg.is_some() {
+                    out.push_str("\x1b[0m");
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Print the ANSI rendering to stderr (keeps stdout for answers).
+    pub fn print(&self) {
+        eprint!("{}", self.ansi());
+    }
+
+    /// RGB pixels with each cell drawn as a `cell_size` square: `(width, height, rgb bytes)`.
+    pub fn pixels(&self, cell_size: u32) -> (u32, u32, Vec<u8>) {
+        let rows = self.rows();
+        let (w, h) = (
+            self.grid.size.0.max(0) as u32,
+            self.grid.size.1.max(0) as u32,
+        );
+        let (pw, ph) = (w * cell_size, h * cell_size);
+        let mut data = Vec::with_capacity((pw * ph * 3) as usize);
+        for py in 0..ph {
+            let y = py / cell_size;
+            for px in 0..pw {
+                let x = px / cell_size;
+                let Rgb(r, g, b) = self.fill((x as i32, y as i32), rows[y as usize][x as usize]);
+                data.extend_from_slice(&[r, g, b]);
+            }
+        }
+        (pw, ph, data)
+    }
+
+    /// Write a binary PPM (P6) image.
+    pub fn write_ppm(&self, path: impl AsRef<Path>, cell_size: u32) -> Result<()> {
+        let (w, h, data) = self.pixels(cell_size);
+        let mut out = BufWriter::new(File::create(path)?);
+        write!(out, "P6\n{w} {h}\n255\n")?;
+        out.write_all(&data)?;
+        Ok(())
+    }
+
+    /// Write a PNG image.
+    pub fn write_png(&self, path: impl AsRef<Path>, cell_size: u32) -> Result<()> {
+        let (w, h, data) = self.pixels(cell_size);
+        let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), w, h);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&data)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.to_rows(None, None) {
+            writeln!(f, "{}", row.into_iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "#####\n#S.E#\n#####";
+
+    #[test]
+    fn display_is_plain_rows() {
+        assert_eq!(Grid::from_str(MAZE).to_string(), format!("{MAZE}\n"));
+    }
+
+    #[test]
+    fn ansi_colours_palette_and_highlights() {
+        let grid = Grid::from_str(MAZE);
+        let out = Renderer::new(&grid)
+            .colour('#', Rgb::GREY)
+            .highlight([(2, 1)], Rgb::RED)
+            .ansi();
+        assert!(out.starts_with("\x1b[38;2;128;128;128m#\x1b[0m"));
+        assert!(out.contains("\x1b[48;2;220;50;47m.\x1b[0m"));
+        assert_eq!(out.lines().count(), 3);
+    }
+
+    #[test]
+    fn image_export() {
+        let grid = Grid::from_str(MAZE);
+        let renderer = Renderer::new(&grid).highlight([(1, 1)], Rgb::GREEN);
+        let (w, h, data) = renderer.pixels(2);
+        assert_eq!((w, h, data.len()), (10, 6, 180));
+        // Pixel (2, 2) is cell (1, 1).
+        assert_eq!(&data[(2 * 10 + 2) * 3..][..3], &[133, 153, 0]);
+
+        let dir = std::env::temp_dir();
+        let ppm = dir.join("aoc25-render-test.ppm");
+        renderer.write_ppm(&ppm, 1).unwrap();
+        assert!(std::fs::read(&ppm).unwrap().starts_with(b"P6\n5 3\n255\n"));
+        let png = dir.join("aoc25-render-test.png");
+        renderer.write_png(&png, 3).unwrap();
+        assert!(std::fs::read(&png).unwrap().starts_with(b"\x89PNG"));
+        let _ = std::fs::remove_file(ppm);
+        let _ = std::fs::remove_file(png);
+    }
+}
@@ -0,0 +1,221 @@
+//! Declarative parsing of inputs made of blank-line separated sections.
+//!
+//! A schema is a tuple of named `Section`s, one per paragraph of the input; parsing it gives a
+//! tuple of their typed values, or a `ParseError` naming the section that failed:
+//!
+//! ```ignore
+//! // 2024 day 15: a warehouse map, then the robot's moves.
+//! let (warehouse, moves) = parse_schema(
+//!     input,
+//!     (grid("warehouse"), chars_of("moves", Direction::from_char)),
+//! )?;
+//! ```
+
+use super::error::ParseError;
+use super::grid::Grid;
+use super::{lines, show_debug_items, show_items};
+use color_eyre::eyre::Result;
+use std::fmt::Debug;
+
+type SectionParser<T> = Box<dyn Fn(&str) -> Result<T, ParseError>>;
+
+/// A named parser for one section of the input.
+pub struct Section<T> {
+    name: &'static str,
+    parse: SectionParser<T>,
+}
+
+impl<T> Section<T> {
+    pub fn new(
+        name: &'static str,
+        parse: impl Fn(&str) -> Result<T, ParseError> + 'static,
+    ) -> Self {
+        Section {
+            name,
+            parse: Box::new(parse),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn parse(&self, text: &str) -> Result<T, ParseError> {
+        (self.parse)(text)
+    }
+}
+
+/// A section parsed as a whole by `parser`.
+pub fn whole<T: 'static>(
+    name: &'static str,
+    parser: fn(&str) -> Result<T, ParseError>,
+) -> Section<T> {
+    Section::new(name, parser)
+}
+
+/// A section kept as raw text (e.g. a crate drawing to be parsed by hand).
+pub fn raw(name: &'static str) -> Section<String> {
+    Section::new(name, |text| Ok(text.to_string()))
+}
+
+/// A section with one record per line.
+pub fn lines_of<T: 'static>(
+    name: &'static str,
+    parser: fn(&str) -> Result<T, ParseError>,
+) -> Section<Vec<T>> {
+    Section::new(name, move |text| {
+        lines(text).into_iter().map(parser).collect()
+    })
+}
+
+/// A section of single-character records, ignoring whitespace and line breaks (e.g. `<^^>v`).
+pub fn chars_of<T: 'static>(name: &'static str, parser: fn(char) -> Option<T>) -> Section<Vec<T>> {
+    Section::new(name, move |text| {
+        text.char_indices()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(i, c)| {
+                parser(c).ok_or_else(|| {
+                    ParseError::at(text, &text[i..i + c.len_utf8()], "unexpected character")
+                })
+            })
+            .collect()
+    })
+}
+
+/// A section holding a character grid.
+pub fn grid(name: &'static str) -> Section<Grid> {
+    Section::new(name, |text| Ok(Grid::from_str(text)))
+}
+
+/// A sequence of sections: implemented for tuples of up to four `Section`s.
+pub trait Schema {
+    type Output;
+
+    fn names(&self) -> Vec<&'static str>;
+
+    /// Parse one paragraph per section; `parts` has exactly as many entries as the schema.
+    fn parse_parts(&self, parts: &[&str]) -> Result<Self::Output, ParseError>;
+}
+
+macro_rules! impl_schema {
+    ($($t:ident $i:tt),+) => {
+        impl<$($t),+> Schema for ($(Section<$t>,)+) {
+            type Output = ($($t,)+);
+
+            fn names(&self) -> Vec<&'static str> {
+                vec![$(self.$i.name),+]
+            }
+
+            fn parse_parts(&self, parts: &[&str]) -> Result<Self::Output, ParseError> {
+                Ok(($(
+                    self.$i
+                        .parse(parts[$i])
+                        .map_err(|e| e.in_named_section($i, self.$i.name))?,
+                )+))
+            }
+        }
+    };
+}
+
+impl_schema!(A 0);
+impl_schema!(A 0, B 1);
+impl_schema!(A 0, B 1, C 2);
+impl_schema!(A 0, B 1, C 2, D 3);
+
+fn parse_located<S: Schema>(text: &str, schema: &S) -> Result<S::Output, ParseError> {
+    let text = text.trim_end();
+    let parts: Vec<&str> = text.split("\n\n").collect();
+    let names = schema.names();
+    if parts.len() != names.len() {
+        let found = parts.len();
+        // Point at the first extra section, or at the end of the input if one is missing.
+        let at = parts
+            .get(names.len())
+            .copied()
+            .unwrap_or(&text[text.len()..]);
+        return Err(ParseError::at(
+            text,
+            at,
+            format!(
+                "expected {} sections ({}) separated by blank lines, but there are {found}",
+                names.len(),
+                names.join(", ")
+            ),
+        ));
+    }
+    schema.parse_parts(&parts).map_err(|e| e.within(text))
+}
+
+/// Parse `text` as the sections of `schema`.
+pub fn parse_schema<S: Schema>(text: &str, schema: S) -> Result<S::Output> {
+    parse_located(text, &schema).map_err(ParseError::report)
+}
+
+/// Variant of `parse_schema` that previews the input lines and the parsed value.
+pub fn parse_schema_with_preview<S: Schema>(text: &str, schema: S) -> Result<S::Output>
+where
+    S::Output: Debug,
+{
+    show_items("Puzzle input", &lines(text));
+    let parsed = parse_schema(text, schema)?;
+    show_debug_items("Parsed representation", std::slice::from_ref(&parsed));
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{Direction, try_positive_ints};
+
+    fn range(line: &str) -> Result<(u64, u64), ParseError> {
+        match try_positive_ints(line)?[..] {
+            [lo, hi] => Ok((lo, hi)),
+            _ => Err(ParseError::at(line, line, "expected a range")),
+        }
+    }
+
+    fn id(line: &str) -> Result<u64, ParseError> {
+        ParseError::parse_field(line, line, "an ID")
+    }
+
+    type Inventory = (Section<Vec<(u64, u64)>>, Section<Vec<u64>>);
+
+    fn schema() -> Inventory {
+        (
+            lines_of("fresh ranges", range),
+            lines_of("ingredient IDs", id),
+        )
+    }
+
+    #[test]
+    fn typed_sections() {
+        let (ranges, ids) = parse_schema("3-5\n10-14\n\n1\n5\n", schema()).unwrap();
+        assert_eq!(ranges, [(3, 5), (10, 14)]);
+        assert_eq!(ids, [1, 5]);
+
+        let (map, moves) = parse_located(
+            "#.@\n\n<^\n>v\n",
+            &(grid("warehouse"), chars_of("moves", Direction::from_char)),
+        )
+        .unwrap();
+        assert_eq!(map.size, (3, 1));
+        assert_eq!(moves.len(), 4);
+    }
+
+    #[test]
+    fn errors_name_the_section() {
+        let err = parse_located("3-5\n\n1\nx\n", &schema()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "section 2 (ingredient IDs), line 4, column 1: expected an ID, found \"x\""
+        );
+        let err = parse_located("3-5\n10-14\n", &schema()).unwrap_err();
+        assert!(
+            err.message
+                .contains("expected 2 sections (fresh ranges, ingredient IDs)")
+        );
+        assert_eq!(err.line, 2);
+        let err = parse_located("1\n\n2\n\n3", &(raw("a"), raw("b"))).unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (5, "3"));
+    }
+}
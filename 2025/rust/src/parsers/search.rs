@@ -0,0 +1,452 @@
+//! Shortest-path search over arbitrary state spaces, with `Grid` conveniences.
+//!
+//! States are any `Clone + Eq + Hash` type (a `Point`, or `(Point, Direction)` for a reindeer
+//! that pays to turn). Successor functions return the next states (BFS) or `(state, cost)` pairs
+//! (Dijkstra, A*). Searches record every predecessor on a shortest path, so the result doubles as
+//! the all-shortest-paths DAG.
+
+use super::grid::{Grid, Point};
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Costs usable by Dijkstra and A*: `Default` must be zero.
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+impl<C: Copy + Ord + Default + Add<Output = C>> Cost for C {}
+
+/// Distances from the start state(s) and the predecessor DAG of all shortest paths.
+#[derive(Debug, Clone)]
+pub struct Paths<S, C> {
+    pub dist: HashMap<S, C>,
+    preds: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Paths<S, C> {
+    /// Shortest distance to `s`, if reached.
+    pub fn distance(&self, s: &S) -> Option<C> {
+        self.dist.get(s).copied()
+    }
+
+    /// Every predecessor of `s` on some shortest path (empty for start states).
+    pub fn predecessors(&self, s: &S) -> &[S] {
+        self.preds.get(s).map_or(&[], Vec::as_slice)
+    }
+
+    /// One shortest path from a start state to `goal`, inclusive of both ends.
+    pub fn path_to(&self, goal: &S) -> Option<Vec<S>> {
+        self.dist.get(goal)?;
+        let mut path = vec![goal.clone()];
+        while let Some(prev) = self.predecessors(path.last().unwrap()).first() {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// All states lying on any shortest path to `goal` (including `goal` and the start).
+    pub fn on_shortest_paths(&self, goal: &S) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        if !self.dist.contains_key(goal) {
+            return seen;
+        }
+        let mut stack = vec![goal.clone()];
+        while let Some(s) = stack.pop() {
+            if seen.insert(s.clone()) {
+                stack.extend(self.predecessors(&s).iter().cloned());
+            }
+        }
+        seen
+    }
+
+    /// Every shortest path to `goal`. The count can grow exponentially; prefer
+    /// `on_shortest_paths` when only the cells matter.
+    pub fn all_paths_to(&self, goal: &S) -> Vec<Vec<S>> {
+        if !self.dist.contains_key(goal) {
+            return vec![];
+        }
+        let preds = self.predecessors(goal);
+        if preds.is_empty() {
+            return vec![vec![goal.clone()]];
+        }
+        preds
+            .iter()
+            .flat_map(|p| self.all_paths_to(p))
+            .map(|mut path| {
+                path.push(goal.clone());
+                path
+            })
+            .collect()
+    }
+}
+
+/// Breadth-first search from `starts`, exploring everything reachable. Distances count steps.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+) -> Paths<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut dist = HashMap::new();
+    let mut preds: HashMap<S, Vec<S>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    for s in starts {
+        if dist.insert(s.clone(), 0).is_none() {
+            queue.push_back(s);
+        }
+    }
+    while let Some(s) = queue.pop_front() {
+        let d = dist[&s] + 1;
+        for next in successors(&s) {
+            match dist.entry(next.clone()) {
+                Entry::Vacant(e) => {
+                    e.insert(d);
+                    preds.insert(next.clone(), vec![s.clone()]);
+                    queue.push_back(next);
+                }
+                Entry::Occupied(e) if *e.get() == d => {
+                    preds.entry(next).or_default().push(s.clone());
+                }
+                Entry::Occupied(_) => {}
+            }
+        }
+    }
+    Paths { dist, preds }
+}
+
+/// Dijkstra's algorithm from `starts` with per-edge costs, exploring everything reachable.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+) -> Paths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut frontier = Frontier::default();
+    let mut dist = HashMap::new();
+    let mut preds: HashMap<S, Vec<S>> = HashMap::new();
+    for s in starts {
+        dist.insert(s.clone(), C::default());
+        frontier.push(C::default(), s);
+    }
+    while let Some((d, s)) = frontier.pop() {
+        if dist.get(&s).is_some_and(|&best| d > best) {
+            continue;
+        }
+        for (next, cost) in successors(&s) {
+            relax(
+                &mut dist,
+                &mut preds,
+                &mut frontier,
+                &s,
+                next,
+                d + cost,
+                |_| C::default(),
+            );
+        }
+    }
+    Paths { dist, preds }
+}
+
+/// A* from `start` until `is_goal`, guided by an admissible `heuristic` (never overestimates).
+/// Returns the cost and one cheapest path, or `None` if no goal is reachable.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    heuristic: impl Fn(&S) -> C,
+    is_goal: impl Fn(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut frontier = Frontier::default();
+    let mut dist = HashMap::new();
+    let mut preds: HashMap<S, Vec<S>> = HashMap::new();
+    dist.insert(start.clone(), C::default());
+    frontier.push(heuristic(&start), start);
+    while let Some((priority, s)) = frontier.pop() {
+        let d = dist[&s];
+        if priority > d + heuristic(&s) {
+            continue; // superseded by a cheaper route
+        }
+        if is_goal(&s) {
+            let paths = Paths { dist, preds };
+            return paths.path_to(&s).map(|p| (d, p));
+        }
+        for (next, cost) in successors(&s) {
+            relax(
+                &mut dist,
+                &mut preds,
+                &mut frontier,
+                &s,
+                next,
+                d + cost,
+                &heuristic,
+            );
+        }
+    }
+    None
+}
+
+/// Record `next` at cost `d` via `from` if it is no worse than what is known. A tie only adds
+/// `from` as a predecessor when `from` is strictly closer, so zero-cost edges can't make the
+/// predecessor graph cyclic (which would send `path_to` round forever).
+fn relax<S: Clone + Eq + Hash, C: Cost>(
+    dist: &mut HashMap<S, C>,
+    preds: &mut HashMap<S, Vec<S>>,
+    frontier: &mut Frontier<S, C>,
+    from: &S,
+    next: S,
+    d: C,
+    heuristic: impl Fn(&S) -> C,
+) {
+    match dist.get(&next) {
+        Some(&best) if d > best => {}
+        Some(&best) if d == best => {
+            if dist[from] < d {
+                preds.entry(next).or_default().push(from.clone());
+            }
+        }
+        _ => {
+            dist.insert(next.clone(), d);
+            preds.insert(next.clone(), vec![from.clone()]);
+            frontier.push(d + heuristic(&next), next);
+        }
+    }
+}
+
+/// A min-priority queue of states that doesn't need `S: Ord`.
+struct Frontier<S, C> {
+    heap: BinaryHeap<Reverse<(C, usize)>>,
+    states: Vec<Option<S>>,
+}
+
+impl<S, C> Default for Frontier<S, C> {
+    fn default() -> Self {
+        Frontier {
+            heap: BinaryHeap::new(),
+            states: Vec::new(),
+        }
+    }
+}
+
+impl<S, C: Ord + Copy> Frontier<S, C> {
+    fn push(&mut self, priority: C, s: S) {
+        self.heap.push(Reverse((priority, self.states.len())));
+        self.states.push(Some(s));
+    }
+
+    fn pop(&mut self) -> Option<(C, S)> {
+        let Reverse((priority, i)) = self.heap.pop()?;
+        Some((
+            priority,
+            self.states[i].take().expect("each entry is popped once"),
+        ))
+    }
+}
+
+/// Manhattan (taxicab) distance between two points.
+pub fn manhattan(a: Point, b: Point) -> i32 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+impl<T> Grid<T> {
+    /// Neighbours of `p` (by this grid's directions) that can be entered from `p`, where
+    /// `passable(from, to)` sees both cell values, e.g. `|a, b| b <= a + 1` for a height map.
+    pub fn moves(&self, p: Point, passable: impl Fn(&T, &T) -> bool) -> Vec<Point> {
+        let Some(here) = self.get_ref(p) else {
+            return vec![];
+        };
+        self.directions()
+            .iter()
+            .map(|&d| self.step(p, d))
+            .filter(|&q| {
+                (self.is_wrapping() || self.in_range(q))
+                    && self.get_ref(q).is_some_and(|there| passable(here, there))
+            })
+            .collect()
+    }
+
+    /// BFS step counts from `starts` over passable moves.
+    pub fn bfs(
+        &self,
+        starts: impl IntoIterator<Item = Point>,
+        passable: impl Fn(&T, &T) -> bool,
+    ) -> Paths<Point, usize> {
+        bfs(starts, |&p| self.moves(p, &passable))
+    }
+
+    /// Dijkstra from `starts` where entering a cell costs `cost(from, to)`, or is blocked by `None`.
+    pub fn dijkstra<C: Cost>(
+        &self,
+        starts: impl IntoIterator<Item = Point>,
+        cost: impl Fn(&T, &T) -> Option<C>,
+    ) -> Paths<Point, C> {
+        dijkstra(starts, |&p| {
+            let here = self.get_ref(p);
+            self.moves(p, |_, _| true)
+                .into_iter()
+                .filter_map(|q| Some((q, cost(here?, self.get_ref(q)?)?)))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// A lower bound on the steps from `p` to `goal` with this grid's directions: Manhattan
+    /// distance for orthogonal unit steps, Chebyshev once diagonals are allowed, each axis
+    /// measured the short way round when wrapping. Other direction sets (knight moves, say) get
+    /// no estimate, which leaves A* searching like BFS.
+    pub fn steps_lower_bound(&self, p: Point, goal: Point) -> usize {
+        let axis = |a: i32, b: i32, size: i32| {
+            let d = (a - b).abs();
+            if self.is_wrapping() && size > 0 {
+                let d = d % size;
+                d.min(size - d)
+            } else {
+                d
+            }
+        };
+        let dx = axis(p.0, goal.0, self.size.0) as usize;
+        let dy = axis(p.1, goal.1, self.size.1) as usize;
+        let directions = self.directions();
+        if directions.iter().all(|&(x, y)| x.abs() + y.abs() <= 1) {
+            dx + dy
+        } else if directions
+            .iter()
+            .all(|&(x, y)| x.abs() <= 1 && y.abs() <= 1)
+        {
+            dx.max(dy)
+        } else {
+            0
+        }
+    }
+
+    /// Fewest steps from `start` to `goal` over passable moves, with the path taken. This is A*
+    /// guided by `steps_lower_bound`, so it stays exact on 8-connected and wrapping grids.
+    pub fn shortest_path(
+        &self,
+        start: Point,
+        goal: Point,
+        passable: impl Fn(&T, &T) -> bool,
+    ) -> Option<(usize, Vec<Point>)> {
+        astar(
+            start,
+            |&p| self.moves(p, &passable).into_iter().map(|q| (q, 1usize)),
+            |&p| self.steps_lower_bound(p, goal),
+            |&p| p == goal,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{DIRECTIONS4, DIRECTIONS8, Missing};
+
+    /// 2022 day 12's example height map.
+    const HILL: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi";
+
+    fn height(c: char) -> u8 {
+        match c {
+            'S' => b'a',
+            'E' => b'z',
+            c => c as u8,
+        }
+    }
+
+    #[test]
+    fn bfs_on_height_map() {
+        let grid = Grid::from_str(HILL);
+        let (start, goal) = (grid.find_all(['S'])[0], grid.find_all(['E'])[0]);
+        let heights = grid.map(|&c| height(c));
+        let paths = heights.bfs([start], |&a, &b| b <= a + 1);
+        assert_eq!(paths.distance(&goal), Some(31));
+        assert_eq!(paths.path_to(&goal).unwrap().len(), 32);
+        let (steps, _) = heights
+            .shortest_path(start, goal, |&a, &b| b <= a + 1)
+            .unwrap();
+        assert_eq!(steps, 31);
+    }
+
+    #[test]
+    fn all_shortest_paths_through_open_square() {
+        let grid = Grid::from_str("...\n...\n...");
+        let paths = grid.bfs([(0, 0)], |_, _| true);
+        assert_eq!(paths.all_paths_to(&(2, 2)).len(), 6);
+        assert_eq!(paths.on_shortest_paths(&(2, 2)).len(), 9);
+        assert_eq!(paths.on_shortest_paths(&(1, 0)).len(), 2);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_on_state_graph() {
+        // Turning costs 1000, stepping costs 1: state is (position, heading).
+        let step = |&(x, dir): &(i32, i32)| vec![((x + dir, dir), 1), ((x, -dir), 1000)];
+        let paths = dijkstra([(0, 1)], |s| {
+            step(s).into_iter().filter(|((x, _), _)| x.abs() <= 3)
+        });
+        assert_eq!(paths.distance(&(-2, -1)), Some(1002));
+        let (cost, path) = astar((0, 1), step, |&(x, _)| (x + 2).abs(), |&(x, _)| x == -2).unwrap();
+        assert_eq!(cost, 1002);
+        assert_eq!(path.first(), Some(&(0, 1)));
+        assert_eq!(path.last(), Some(&(-2, -1)));
+    }
+
+    #[test]
+    fn grid_dijkstra_uses_cell_costs() {
+        let grid = Grid::parse_with("131\n191\n111", &DIRECTIONS4, Missing::Error, |c| {
+            c.to_digit(10).unwrap()
+        });
+        let paths = grid.dijkstra([(0, 0)], |_, &to| Some(to));
+        assert_eq!(paths.distance(&(2, 2)), Some(4));
+        let walls = Grid::from_str(".#.\n...");
+        assert_eq!(
+            walls
+                .shortest_path((0, 0), (2, 0), |_, &b| b != '#')
+                .unwrap()
+                .0,
+            4
+        );
+    }
+
+    #[test]
+    fn shortest_path_with_diagonals_and_wrapping() {
+        // Diagonal moves make Manhattan distance overestimate: the goal is 4 steps away, not 8.
+        let open = Grid::filled((5, 5), '.', &DIRECTIONS8, Missing::Error);
+        assert_eq!(open.steps_lower_bound((0, 0), (4, 4)), 4);
+        let (steps, path) = open.shortest_path((0, 0), (4, 4), |_, _| true).unwrap();
+        assert_eq!((steps, path.len()), (4, 5));
+
+        // Round the edge of a torus, the far corner is next door.
+        let torus = Grid::filled((5, 5), '.', &DIRECTIONS4, Missing::Error).wrapping(true);
+        assert_eq!(torus.steps_lower_bound((0, 0), (4, 4)), 2);
+        let (steps, _) = torus.shortest_path((0, 0), (4, 4), |_, _| true).unwrap();
+        assert_eq!(steps, 2);
+        let ring = Grid::from_str("..#..").wrapping(true);
+        assert_eq!(
+            ring.shortest_path((1, 0), (3, 0), |_, &b| b != '#')
+                .unwrap()
+                .0,
+            3
+        );
+    }
+
+    #[test]
+    fn zero_cost_edges_keep_paths_finite() {
+        // Teleporting between 0 and 1 is free, so each is reached as cheaply from the other.
+        let paths = dijkstra([0], |&x: &i32| match x {
+            0 => vec![(1, 0), (2, 5)],
+            1 => vec![(0, 0), (2, 3)],
+            _ => vec![],
+        });
+        assert_eq!(paths.distance(&2), Some(3));
+        assert!(paths.predecessors(&0).is_empty());
+        assert_eq!(paths.path_to(&2).unwrap(), [0, 1, 2]);
+        assert_eq!(paths.all_paths_to(&2), [vec![0, 1, 2]]);
+    }
+}
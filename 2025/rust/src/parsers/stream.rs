@@ -0,0 +1,240 @@
+//! Streaming, allocation-free splitters and scanners for very large inputs.
+//!
+//! These are the iterator forms of `lines`, `paragraphs`, `ints`, `digits`, `words`, `atoms` and
+//! `parse_text`: they borrow from the input and allocate nothing per item, so a several-hundred-MB
+//! input (see `core::Input`, which memory-maps big files) can be folded over in one pass:
+//!
+//! ```ignore
+//! let total: i64 = lines_iter(&input).flat_map(ints_iter).sum();
+//! ```
+//!
+//! Integers are found by `IntScanner`, a hand-written byte scanner that matches exactly what the
+//! `-?[0-9]+` regex used to, without the regex engine; `AtomScanner` does the same for atoms.
+
+use super::{Atom, atom};
+use std::iter::FusedIterator;
+
+/// Lines of `text`, without their `\n` or `\r\n`.
+pub fn lines_iter(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+}
+
+/// Paragraphs of `text` separated by a blank line ("\n\n").
+pub fn paragraphs_iter(text: &str) -> impl Iterator<Item = &str> {
+    text.split("\n\n")
+}
+
+/// Lazily parse each section of `text` (as split by `sections`) with `parser`.
+pub fn parse_iter<'a, T, I>(
+    text: &'a str,
+    parser: impl FnMut(&'a str) -> T,
+    sections: impl FnOnce(&'a str) -> I,
+) -> impl Iterator<Item = T>
+where
+    I: Iterator<Item = &'a str>,
+{
+    sections(text.trim_end()).map(parser)
+}
+
+/// The integer lexemes in a string: runs of ASCII digits, with a directly preceding `-` when
+/// scanning signed integers.
+#[derive(Debug, Clone)]
+pub struct IntScanner<'a> {
+    text: &'a str,
+    pos: usize,
+    signed: bool,
+}
+
+impl<'a> IntScanner<'a> {
+    /// Scan for `-?[0-9]+`.
+    pub fn signed(text: &'a str) -> Self {
+        IntScanner {
+            text,
+            pos: 0,
+            signed: true,
+        }
+    }
+
+    /// Scan for `[0-9]+`.
+    pub fn unsigned(text: &'a str) -> Self {
+        IntScanner {
+            text,
+            pos: 0,
+            signed: false,
+        }
+    }
+}
+
+impl<'a> Iterator for IntScanner<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let bytes = self.text.as_bytes();
+        let first = self.pos + bytes[self.pos..].iter().position(u8::is_ascii_digit)?;
+        let start = if self.signed && first > self.pos && bytes[first - 1] == b'-' {
+            first - 1
+        } else {
+            first
+        };
+        let end = bytes[first..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .map_or(bytes.len(), |n| first + n);
+        self.pos = end;
+        // Only ASCII bytes were examined, so both ends are char boundaries.
+        Some(&self.text[start..end])
+    }
+}
+
+impl FusedIterator for IntScanner<'_> {}
+
+/// The magnitude of a run of ASCII digits, or `None` on overflow.
+fn digits_value(digits: &[u8]) -> Option<u64> {
+    digits.iter().try_fold(0u64, |acc, &d| {
+        acc.checked_mul(10)?.checked_add(u64::from(d - b'0'))
+    })
+}
+
+/// Convert an `IntScanner::signed` lexeme to an `i64`, or `None` on overflow.
+pub fn lexeme_to_i64(lexeme: &str) -> Option<i64> {
+    match lexeme.as_bytes() {
+        [b'-', digits @ ..] => 0i64.checked_sub_unsigned(digits_value(digits)?),
+        digits => i64::try_from(digits_value(digits)?).ok(),
+    }
+}
+
+/// All integers in `text` (negative allowed), skipping any that overflow an `i64`.
+pub fn ints_iter(text: &str) -> impl Iterator<Item = i64> {
+    IntScanner::signed(text).filter_map(lexeme_to_i64)
+}
+
+/// All non-negative integers in `text`, skipping any that overflow a `u64`.
+pub fn positive_ints_iter(text: &str) -> impl Iterator<Item = u64> {
+    IntScanner::unsigned(text).filter_map(|lexeme| digits_value(lexeme.as_bytes()))
+}
+
+/// All single ASCII digits in `text` as integers 0–9.
+pub fn digits_iter(text: &str) -> impl Iterator<Item = u8> {
+    text.bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|digit| digit - b'0')
+}
+
+/// All runs of ASCII letters in `text`.
+pub fn words_iter(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| !word.is_empty())
+}
+
+/// The atom lexemes in a string: optionally signed integers or decimals (`[+-]?[0-9]+(\.[0-9]+)?`),
+/// else runs of alphanumerics and `_`. A digit always starts a number, so `12ab` is `12`, `ab`.
+#[derive(Debug, Clone)]
+pub struct AtomScanner<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> AtomScanner<'a> {
+    pub fn new(text: &'a str) -> Self {
+        AtomScanner { text, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for AtomScanner<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let bytes = self.text.as_bytes();
+        let digit_at = |i: usize| bytes.get(i).is_some_and(u8::is_ascii_digit);
+        let digits_from =
+            |i: usize| i + bytes[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        for (offset, c) in self.text[self.pos..].char_indices() {
+            let start = self.pos + offset;
+            let end = if c.is_ascii_digit() || (matches!(c, '+' | '-') && digit_at(start + 1)) {
+                let whole = digits_from(start + 1);
+                if bytes.get(whole) == Some(&b'.') && digit_at(whole + 1) {
+                    digits_from(whole + 1)
+                } else {
+                    whole
+                }
+            } else if is_word(c) {
+                self.text[start..]
+                    .char_indices()
+                    .find(|&(_, c)| !is_word(c))
+                    .map_or(self.text.len(), |(n, _)| start + n)
+            } else {
+                continue;
+            };
+            self.pos = end;
+            return Some(&self.text[start..end]);
+        }
+        self.pos = self.text.len();
+        None
+    }
+}
+
+impl FusedIterator for AtomScanner<'_> {}
+
+/// All atoms (numbers or identifiers) in `text`, skipping punctuation.
+pub fn atoms_iter(text: &str) -> impl Iterator<Item = Atom> {
+    AtomScanner::new(text).map(atom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scanner_matches_the_int_regex() {
+        let text = "p=0,-4 v=3--3 x- 12-34 -0 é9 99999999999999999999 -9223372036854775808";
+        let lexemes: Vec<&str> = IntScanner::signed(text).collect();
+        assert_eq!(
+            lexemes,
+            [
+                "0",
+                "-4",
+                "3",
+                "-3",
+                "12",
+                "-34",
+                "-0",
+                "9",
+                "99999999999999999999",
+                "-9223372036854775808"
+            ]
+        );
+        let ints: Vec<i64> = ints_iter(text).collect();
+        assert_eq!(ints, [0, -4, 3, -3, 12, -34, 0, 9, i64::MIN]);
+        let positive: Vec<u64> = positive_ints_iter("3-5, 10-14").collect();
+        assert_eq!(positive, [3, 5, 10, 14]);
+        assert_eq!(IntScanner::signed("no numbers").next(), None);
+    }
+
+    #[test]
+    fn scanner_finds_atoms() {
+        let text = "a-b +-3 1.2.3 x_1 -.5 12ab é9, 4. ";
+        let lexemes: Vec<&str> = AtomScanner::new(text).collect();
+        assert_eq!(
+            lexemes,
+            [
+                "a", "b", "-3", "1.2", "3", "x_1", "5", "12", "ab", "é9", "4"
+            ]
+        );
+        assert_eq!(
+            atoms_iter("x=+7").map(|a| a.as_int()).collect::<Vec<_>>(),
+            [None, Some(7)]
+        );
+        assert_eq!(AtomScanner::new(" ,. ").next(), None);
+    }
+
+    #[test]
+    fn lazy_splitters() {
+        let text = "ab1 cd\n\n2x3\n";
+        assert_eq!(paragraphs_iter(text).count(), 2);
+        assert_eq!(words_iter(text).collect::<Vec<_>>(), ["ab", "cd", "x"]);
+        assert_eq!(digits_iter(text).collect::<Vec<_>>(), [1, 2, 3]);
+        let sums: Vec<i64> = parse_iter(text, |line| ints_iter(line).sum(), lines_iter).collect();
+        assert_eq!(sums, [1, 0, 5]);
+    }
+}
@@ -0,0 +1,217 @@
+//! Grid transforms: rotations, flips, transpose, cropping and tiling.
+//!
+//! Every transform returns a new grid with the same storage kind, directions, missing policy and
+//! wrap mode. Sparse cells outside `size` are dropped by transforms defined relative to `size`.
+
+use super::grid::{Grid, Point};
+use std::ops::Range;
+
+impl<T: Clone> Grid<T> {
+    /// Build a grid of `size` from already-moved cells, keeping this grid's settings.
+    fn rebuild(&self, size: (i32, i32), cells: impl Iterator<Item = (Point, T)>) -> Self {
+        let grid = if self.is_dense() {
+            let mut slots: Vec<Option<T>> = vec![None; (size.0.max(0) * size.1.max(0)) as usize];
+            for ((x, y), t) in cells {
+                if 0 <= x && x < size.0 && 0 <= y && y < size.1 {
+                    slots[(y * size.0 + x) as usize] = Some(t);
+                }
+            }
+            let dense: Option<Vec<T>> = slots.into_iter().collect();
+            let dense = dense.expect("dense transforms fill every cell");
+            Grid::from_vec(size, dense, self.directions(), self.missing().clone())
+        } else {
+            Grid::from_cells(size, cells, self.directions(), self.missing().clone())
+        };
+        grid.wrapping(self.is_wrapping())
+    }
+
+    /// Cells within `size`, for transforms that only make sense on the grid's rectangle.
+    fn in_range_cells(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.iter().filter(|&(p, _)| self.in_range(p))
+    }
+
+    /// Rotate 90° clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let (w, h) = self.size;
+        self.rebuild(
+            (h, w),
+            self.in_range_cells()
+                .map(|((x, y), t)| ((h - 1 - y, x), t.clone())),
+        )
+    }
+
+    /// Rotate 180°.
+    pub fn rotate_180(&self) -> Self {
+        let (w, h) = self.size;
+        self.rebuild(
+            (w, h),
+            self.in_range_cells()
+                .map(|((x, y), t)| ((w - 1 - x, h - 1 - y), t.clone())),
+        )
+    }
+
+    /// Rotate 90° counter-clockwise (270° clockwise).
+    pub fn rotate_ccw(&self) -> Self {
+        let (w, h) = self.size;
+        self.rebuild(
+            (h, w),
+            self.in_range_cells()
+                .map(|((x, y), t)| ((y, w - 1 - x), t.clone())),
+        )
+    }
+
+    /// Mirror left-to-right.
+    pub fn flip_horizontal(&self) -> Self {
+        let (w, h) = self.size;
+        self.rebuild(
+            (w, h),
+            self.in_range_cells()
+                .map(|((x, y), t)| ((w - 1 - x, y), t.clone())),
+        )
+    }
+
+    /// Mirror top-to-bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let (w, h) = self.size;
+        self.rebuild(
+            (w, h),
+            self.in_range_cells()
+                .map(|((x, y), t)| ((x, h - 1 - y), t.clone())),
+        )
+    }
+
+    /// Swap rows and columns (reflect in the main diagonal).
+    pub fn transpose(&self) -> Self {
+        let (w, h) = self.size;
+        self.rebuild(
+            (h, w),
+            self.in_range_cells().map(|((x, y), t)| ((y, x), t.clone())),
+        )
+    }
+
+    /// The window `xrange` x `yrange`, re-based so its top-left corner is `(0, 0)`. The window is
+    /// clamped to the grid for dense grids; sparse grids may crop anywhere.
+    pub fn crop(&self, xrange: Range<i32>, yrange: Range<i32>) -> Self {
+        let (xrange, yrange) = if self.is_dense() {
+            (
+                xrange.start.max(0)..xrange.end.min(self.size.0),
+                yrange.start.max(0)..yrange.end.min(self.size.1),
+            )
+        } else {
+            (xrange, yrange)
+        };
+        let size = (xrange.len() as i32, yrange.len() as i32);
+        let (x0, y0) = (xrange.start, yrange.start);
+        self.rebuild(
+            size,
+            self.iter()
+                .filter(|((x, y), _)| xrange.contains(x) && yrange.contains(y))
+                .map(|((x, y), t)| ((x - x0, y - y0), t.clone())),
+        )
+    }
+
+    /// `nx` by `ny` copies of the grid side by side.
+    pub fn tile(&self, nx: usize, ny: usize) -> Self {
+        let (w, h) = self.size;
+        let nx = i32::try_from(nx).expect("tile count fits in i32");
+        let ny = i32::try_from(ny).expect("tile count fits in i32");
+        let cells = self.in_range_cells().flat_map(|((x, y), t)| {
+            (0..ny).flat_map(move |j| (0..nx).map(move |i| ((x + i * w, y + j * h), t.clone())))
+        });
+        self.rebuild((w * nx, h * ny), cells)
+    }
+
+    /// All eight rotations and reflections (the dihedral group), starting with the grid itself.
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut out = Vec::with_capacity(8);
+        let mut g = self.clone();
+        for _ in 0..4 {
+            out.push(g.clone());
+            out.push(g.flip_horizontal());
+            g = g.rotate_cw();
+        }
+        out
+    }
+}
+
+/// Transpose a rectangular `Vec` of rows into a `Vec` of columns.
+pub fn transpose<T: Clone>(rows: &[Vec<T>]) -> Vec<Vec<T>> {
+    let width = rows.first().map_or(0, Vec::len);
+    (0..width)
+        .map(|x| rows.iter().map(|row| row[x].clone()).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{DIRECTIONS8, Missing, MissingChar};
+    use std::collections::HashSet;
+
+    fn rows(g: &Grid) -> Vec<String> {
+        g.to_rows(None, None)
+            .into_iter()
+            .map(|r| r.into_iter().collect())
+            .collect()
+    }
+
+    const L: &str = "ab\ncd\nef";
+
+    #[test]
+    fn rotations_and_flips() {
+        let g = Grid::from_str(L);
+        assert_eq!(rows(&g.rotate_cw()), ["eca", "fdb"]);
+        assert_eq!(rows(&g.rotate_ccw()), ["bdf", "ace"]);
+        assert_eq!(rows(&g.rotate_180()), ["fe", "dc", "ba"]);
+        assert_eq!(rows(&g.flip_horizontal()), ["ba", "dc", "fe"]);
+        assert_eq!(rows(&g.flip_vertical()), ["ef", "cd", "ab"]);
+        assert_eq!(rows(&g.transpose()), ["ace", "bdf"]);
+        assert_eq!(rows(&g.rotate_cw().rotate_ccw()), rows(&g));
+        assert_eq!(g.orientations().len(), 8);
+    }
+
+    #[test]
+    fn transforms_keep_settings() {
+        let skip: HashSet<char> = ['.'].into();
+        let g = Grid::from_str_with("a.\n.b", &DIRECTIONS8, &skip, MissingChar::Value('.'))
+            .wrapping(true);
+        let r = g.rotate_cw();
+        assert!(!r.is_dense());
+        assert!(r.is_wrapping());
+        assert_eq!(r.directions(), &DIRECTIONS8);
+        assert_eq!(r.missing(), &Missing::Value('.'));
+        assert_eq!(rows(&r), [".a", "b."]);
+    }
+
+    #[test]
+    fn crop_and_tile() {
+        let g = Grid::from_str(L);
+        assert_eq!(rows(&g.crop(1..5, 1..3)), ["d", "f"]);
+        assert_eq!(rows(&g.tile(2, 1)), ["abab", "cdcd", "efef"]);
+        assert_eq!(g.tile(3, 2).size, (6, 6));
+        assert_eq!(g.tile(0, 1).size, (0, 3));
+    }
+
+    #[test]
+    fn wrap_around_queries() {
+        // 2024 day 14: a robot at p=2,4 with v=2,-3 on an 11x7 torus is at 1,3 after 5 seconds.
+        let room = Grid::filled((11, 7), '.', &DIRECTIONS8, Missing::Error).wrapping(true);
+        let mut p = (2, 4);
+        for _ in 0..5 {
+            p = room.step(p, (2, -3));
+        }
+        assert_eq!(p, (1, 3));
+        assert_eq!(room.neighbours((0, 0)).len(), 8);
+        assert!(room.neighbours((0, 0)).contains(&(10, 6)));
+        assert_eq!(room.follow_line((9, 0), (1, 0)).len(), 11);
+        assert_eq!(room.get((-1, -1)), Some('.'));
+    }
+
+    #[test]
+    fn transpose_rows() {
+        assert_eq!(
+            transpose(&[vec![1, 2, 3], vec![4, 5, 6]]),
+            [[1, 4], [2, 5], [3, 6]]
+        );
+    }
+}